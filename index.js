@@ -1,9 +1,12 @@
 let auth0 = null;
+let auth_profile_endpoint = null;
 
-window.init_auth = async (domain, client_id) => {
+window.init_auth = async (domain, client_id, audience, profile_endpoint) => {
+    auth_profile_endpoint = profile_endpoint;
     auth0 = await createAuth0Client({
         domain,
         client_id,
+        audience,
     });
 
     const query = window.location.search;
@@ -16,6 +19,31 @@ window.init_auth = async (domain, client_id) => {
     }
 }
 
+window.get_token = async () => {
+    return await auth0.getTokenSilently();
+}
+
+// The Management API doesn't allow to change the username, email or password with a user's token,
+// the backend at `profile_endpoint` changes them for the user of the token.
+window.update_user = async (changes) => {
+    if (!auth_profile_endpoint) {
+        throw "Profile changes aren't configured, set profile_endpoint in auth_config.json";
+    }
+    const token = await auth0.getTokenSilently();
+    const response = await fetch(auth_profile_endpoint, {
+        method: "PATCH",
+        headers: {
+            "Authorization": `Bearer ${token}`,
+            "Content-Type": "application/json",
+        },
+        body: JSON.stringify(changes),
+    });
+    if (!response.ok) {
+        const body = await response.json().catch(() => ({}));
+        throw body.message || `User update failed with status ${response.status}`;
+    }
+}
+
 window.redirect_to_sign_up = async () => {
     await auth0.loginWithRedirect({
         redirect_uri: window.location.origin,
@@ -34,3 +62,21 @@ window.logout = () => {
         returnTo: window.location.origin
     });
 }
+
+window.request_notification_permission = async () => {
+    if ("Notification" in window && Notification.permission === "default") {
+        await Notification.requestPermission();
+    }
+}
+
+window.show_notification = async (title, body) => {
+    if (!("Notification" in window)) {
+        throw "Notifications aren't supported by the browser";
+    }
+    await window.request_notification_permission();
+    if (Notification.permission !== "granted") {
+        throw "Notifications aren't allowed";
+    }
+    const notification = new Notification(title, { body });
+    notification.onclick = () => window.focus();
+}
@@ -0,0 +1,230 @@
+use chrono::{prelude::*, Duration};
+
+use std::collections::BTreeMap;
+
+/// Entries running or lasting longer are suspicious, e.g. a timer left running overnight.
+pub const MAX_USUAL_DURATION_HOURS: i64 = 12;
+
+// ------ Interval ------
+
+/// Time entry as seen by the analysis. Running entries have no `stopped`.
+pub struct Interval<Id> {
+    pub id: Id,
+    pub started: DateTime<Local>,
+    pub stopped: Option<DateTime<Local>>,
+}
+
+// ------ Warning ------
+
+#[derive(Debug, Clone)]
+pub struct Warning<Id> {
+    pub issue: Issue<Id>,
+    pub fix: Option<Fix>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Issue<Id> {
+    Overlap { other: Id, overlap: Duration },
+    NonPositiveDuration,
+    TooLong { duration: Duration },
+}
+
+/// One-click change of the affected entry that resolves the issue.
+#[derive(Debug, Clone, Copy)]
+pub enum Fix {
+    /// Stop when the next entry starts.
+    TrimStopped(DateTime<Local>),
+    /// Start when the previous entry stops.
+    DelayStarted(DateTime<Local>),
+}
+
+// ------ analyze ------
+
+/// Scans all entries and returns the warnings of the affected ones.
+///
+/// Running entries end at `now`.
+pub fn analyze<Id: Ord + Copy>(
+    intervals: impl IntoIterator<Item = Interval<Id>>,
+    now: DateTime<Local>,
+) -> BTreeMap<Id, Vec<Warning<Id>>> {
+    let mut intervals = intervals.into_iter().collect::<Vec<_>>();
+    intervals.sort_by_key(|interval| interval.started);
+
+    let mut warnings = BTreeMap::<Id, Vec<Warning<Id>>>::new();
+    let mut warn = |id, issue, fix| {
+        warnings
+            .entry(id)
+            .or_default()
+            .push(Warning { issue, fix })
+    };
+
+    for (index, interval) in intervals.iter().enumerate() {
+        let stopped = interval.stopped.unwrap_or(now);
+        let duration = stopped - interval.started;
+
+        if interval.stopped.is_some() && duration <= Duration::zero() {
+            warn(interval.id, Issue::NonPositiveDuration, None);
+            continue;
+        }
+        if duration > Duration::hours(MAX_USUAL_DURATION_HOURS) {
+            warn(interval.id, Issue::TooLong { duration }, None);
+        }
+
+        // Intervals are sorted by `started` so only the following ones can start before `stopped`.
+        for next in intervals[index + 1..]
+            .iter()
+            .take_while(|next| next.started < stopped)
+        {
+            let next_stopped = next.stopped.unwrap_or(now);
+            if next_stopped <= next.started {
+                continue;
+            }
+            let overlap = stopped.min(next_stopped) - next.started;
+
+            // Trimming to the same `started` would leave nothing of the entry.
+            let trim_stopped =
+                Some(Fix::TrimStopped(next.started)).filter(|_| interval.started < next.started);
+            warn(
+                interval.id,
+                Issue::Overlap {
+                    other: next.id,
+                    overlap,
+                },
+                trim_stopped,
+            );
+
+            // Delaying `started` past the own `stopped` would create a negative duration.
+            let delay_started =
+                Some(Fix::DelayStarted(stopped)).filter(|_| stopped < next_stopped);
+            warn(
+                next.id,
+                Issue::Overlap {
+                    other: interval.id,
+                    overlap,
+                },
+                delay_started,
+            );
+        }
+    }
+    warnings
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Local> {
+        Local.ymd(2021, 3, 1).and_hms(hour, minute, 0)
+    }
+
+    fn interval(
+        id: u32,
+        started: DateTime<Local>,
+        stopped: Option<DateTime<Local>>,
+    ) -> Interval<u32> {
+        Interval { id, started, stopped }
+    }
+
+    #[wasm_bindgen_test]
+    fn nested() {
+        let warnings = analyze(
+            vec![
+                interval(1, at(9, 0), Some(at(12, 0))),
+                interval(2, at(10, 0), Some(at(11, 0))),
+            ],
+            at(13, 0),
+        );
+        assert!(matches!(
+            warnings[&1][..],
+            [Warning {
+                issue: Issue::Overlap { other: 2, overlap },
+                fix: Some(Fix::TrimStopped(trimmed)),
+            }] if overlap == Duration::hours(1) && trimmed == at(10, 0)
+        ));
+        // Delaying the inner entry to 12:00 would push it past its own `stopped`.
+        assert!(matches!(
+            warnings[&2][..],
+            [Warning {
+                issue: Issue::Overlap { other: 1, .. },
+                fix: None,
+            }]
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn identical_starts() {
+        let warnings = analyze(
+            vec![
+                interval(1, at(9, 0), Some(at(10, 0))),
+                interval(2, at(9, 0), Some(at(11, 0))),
+            ],
+            at(13, 0),
+        );
+        assert!(matches!(
+            warnings[&1][..],
+            [Warning {
+                issue: Issue::Overlap { other: 2, overlap },
+                fix: None,
+            }] if overlap == Duration::hours(1)
+        ));
+        assert!(matches!(
+            warnings[&2][..],
+            [Warning {
+                issue: Issue::Overlap { other: 1, .. },
+                fix: Some(Fix::DelayStarted(delayed)),
+            }] if delayed == at(10, 0)
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn running() {
+        let warnings = analyze(
+            vec![
+                interval(1, at(9, 0), None),
+                interval(2, at(9, 30), Some(at(9, 45))),
+            ],
+            at(10, 0),
+        );
+        assert!(matches!(
+            warnings[&1][..],
+            [Warning {
+                issue: Issue::Overlap { other: 2, overlap },
+                fix: Some(Fix::TrimStopped(trimmed)),
+            }] if overlap == Duration::minutes(15) && trimmed == at(9, 30)
+        ));
+
+        let warnings = analyze(vec![interval(1, at(9, 0), None)], at(22, 0));
+        assert!(matches!(
+            warnings[&1][..],
+            [Warning {
+                issue: Issue::TooLong { duration },
+                fix: None,
+            }] if duration == Duration::hours(13)
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn zero_length() {
+        let warnings = analyze(
+            vec![
+                interval(1, at(8, 0), Some(at(10, 0))),
+                interval(2, at(9, 0), Some(at(9, 0))),
+            ],
+            at(13, 0),
+        );
+        // The empty entry doesn't overlap anything.
+        assert!(!warnings.contains_key(&1));
+        assert!(matches!(
+            warnings[&2][..],
+            [Warning {
+                issue: Issue::NonPositiveDuration,
+                fix: None,
+            }]
+        ));
+    }
+}
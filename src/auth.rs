@@ -0,0 +1,60 @@
+use seed::{futures::future::LocalBoxFuture, prelude::*};
+use serde::Serialize;
+
+// ------ UserChanges ------
+
+/// Profile fields to change. `None` fields are left untouched.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct UserChanges {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+impl UserChanges {
+    pub fn is_empty(&self) -> bool {
+        self.username.is_none() && self.email.is_none() && self.password.is_none()
+    }
+}
+
+// ------ AuthError ------
+
+#[derive(Debug)]
+pub struct AuthError(pub String);
+
+// ------ AuthProvider ------
+
+/// Management endpoints of the auth provider.
+///
+/// Pages depend on this trait instead of the JS glue so it can be replaced by a mock.
+pub trait AuthProvider {
+    fn update_user(&self, changes: UserChanges) -> LocalBoxFuture<'static, Result<(), AuthError>>;
+}
+
+// ------ Auth0 ------
+
+/// A user's access token can only change the user metadata in the Auth0 Management API,
+/// so the profile changes go through `profile_endpoint` from `auth_config.json`,
+/// a backend that calls the Management API for the user of the token.
+pub struct Auth0;
+
+impl AuthProvider for Auth0 {
+    fn update_user(&self, changes: UserChanges) -> LocalBoxFuture<'static, Result<(), AuthError>> {
+        Box::pin(async move {
+            let changes = serde_wasm_bindgen::to_value(&changes)
+                .map_err(|error| AuthError(error.to_string()))?;
+            update_user(changes).await.map_err(|error| {
+                AuthError(error.as_string().unwrap_or_else(|| format!("{:?}", error)))
+            })
+        })
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    async fn update_user(changes: JsValue) -> Result<(), JsValue>;
+}
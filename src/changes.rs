@@ -0,0 +1,201 @@
+use seed::{prelude::*, *};
+
+use chrono::prelude::*;
+
+use crate::error_center::{self, RequestError};
+use crate::graphql;
+use crate::preferences::Preferences;
+use crate::store;
+
+// ------ Changes ------
+
+/// Saving of a page's optimistic updates and the errors of the failed requests.
+#[derive(Default)]
+pub struct Changes {
+    pub status: ChangesStatus,
+    pub errors: Vec<RequestError>,
+}
+
+impl Changes {
+    /// Sends the mutation of an optimistic update, `change` is reverted when it fails.
+    pub fn save<ResponseData: 'static>(
+        &mut self,
+        graphql_config: &graphql::Config,
+        orders: &mut impl Orders<Msg>,
+        operation: cynic::Operation<'static, ResponseData>,
+        change: store::Change,
+    ) {
+        let graphql_config = graphql_config.clone();
+        let replay = graphql::Replay::new(&graphql_config, &operation).ok();
+        self.status.request_sent();
+        orders.perform_cmd(async move {
+            Msg::Saved(
+                change,
+                graphql::send_operation(&graphql_config, operation)
+                    .await
+                    .err()
+                    .map(|error| RequestError { error, replay }),
+            )
+        });
+    }
+
+    /// Sends an already built request, e.g. of a timer, `change` is reverted when it fails.
+    pub fn save_request(
+        &mut self,
+        orders: &mut impl Orders<Msg>,
+        request: graphql::Result<graphql::Replay>,
+        change: store::Change,
+    ) {
+        self.status.request_sent();
+        orders.perform_cmd(async move {
+            let request_error = match request {
+                Ok(replay) => replay.send().await.err().map(|error| RequestError {
+                    error,
+                    replay: Some(replay),
+                }),
+                Err(error) => Some(RequestError { error, replay: None }),
+            };
+            Msg::Saved(change, request_error)
+        });
+    }
+
+    fn replay(&mut self, orders: &mut impl Orders<Msg>, replay: graphql::Replay) {
+        self.status.request_sent();
+        orders.perform_cmd(async move {
+            match replay.send().await {
+                Ok(()) => Msg::Replayed,
+                // The change has been reverted already.
+                Err(error) => Msg::Saved(
+                    store::Change::default(),
+                    Some(RequestError {
+                        error,
+                        replay: Some(replay),
+                    }),
+                ),
+            }
+        });
+    }
+}
+
+// ------ ChangesStatus ------
+
+pub enum ChangesStatus {
+    NoChanges,
+    /// `failed` is set when any of the concurrent requests has failed.
+    Saving { requests_in_flight: usize, failed: bool },
+    Saved(DateTime<Local>),
+}
+
+impl Default for ChangesStatus {
+    fn default() -> Self {
+        Self::NoChanges
+    }
+}
+
+impl ChangesStatus {
+    pub fn request_sent(&mut self) {
+        match self {
+            Self::Saving { requests_in_flight, .. } => *requests_in_flight += 1,
+            _ => {
+                *self = Self::Saving {
+                    requests_in_flight: 1,
+                    failed: false,
+                }
+            }
+        }
+    }
+
+    /// Responses of requests sent before the page was opened are ignored.
+    pub fn request_finished(&mut self, succeeded: bool) {
+        if let Self::Saving { requests_in_flight, failed } = self {
+            *failed = *failed || not(succeeded);
+            if *requests_in_flight > 1 {
+                *requests_in_flight -= 1;
+            } else if *failed {
+                *self = Self::NoChanges;
+            } else {
+                *self = Self::Saved(chrono::Local::now());
+            }
+        }
+    }
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    Saved(store::Change, Option<RequestError>),
+    Replayed,
+    ClearErrors,
+    DismissError(usize),
+    RetryError(usize),
+}
+
+/// Returns the change of a failed request, the page has to revert it.
+pub fn update(
+    msg: Msg,
+    changes: &mut Changes,
+    orders: &mut impl Orders<Msg>,
+) -> Option<store::Change> {
+    match msg {
+        Msg::Saved(_, None) => {
+            log!("Msg::ChangesSaved");
+            changes.status.request_finished(true);
+        }
+        Msg::Saved(change, Some(request_error)) => {
+            log!("Msg::ChangesSaved", request_error);
+            changes.errors.push(request_error);
+            changes.status.request_finished(false);
+            return Some(change).filter(|change| not(change.is_empty()));
+        }
+        Msg::Replayed => {
+            log!("Msg::ChangesReplayed");
+            changes.status.request_finished(true);
+            // The store was reloaded without the changes when they failed.
+            orders.notify(store::Invalidate);
+        }
+        Msg::ClearErrors => {
+            changes.errors.clear();
+        }
+        Msg::DismissError(index) => {
+            error_center::remove_group(&mut changes.errors, index);
+        }
+        Msg::RetryError(index) => {
+            let replays = error_center::remove_group(&mut changes.errors, index)
+                .into_iter()
+                .filter_map(|request_error| request_error.replay);
+            for replay in replays {
+                changes.replay(orders, replay);
+            }
+        }
+    }
+    None
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view_status<Ms>(status: &ChangesStatus, preferences: &Preferences) -> Node<Ms> {
+    match status {
+        ChangesStatus::NoChanges => empty![],
+        ChangesStatus::Saving { requests_in_flight, .. } => span![
+            C!["tag", "is-warning", "ml-4"],
+            format!("Saving ({})", requests_in_flight),
+        ],
+        ChangesStatus::Saved(saved) => span![
+            C!["tag", "is-primary", "ml-4"],
+            format!("Saved {}", preferences.format_time(saved)),
+        ],
+    }
+}
+
+pub fn view_errors(changes: &Changes) -> Node<Msg> {
+    error_center::view(
+        &changes.errors,
+        Msg::DismissError,
+        Some(Msg::RetryError),
+        || Msg::ClearErrors,
+    )
+}
@@ -0,0 +1,221 @@
+use seed::prelude::*;
+use chrono::Duration;
+
+use std::fmt;
+
+/// The schema stores durations as `Int` seconds.
+const MAX_SECONDS: f64 = i32::MAX as f64;
+
+// ------ ParseError ------
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    Empty,
+    InvalidNumber(String),
+    UnknownUnit(String),
+    MissingUnit(String),
+    InvalidClockFormat(String),
+    MinutesOutOfRange(u32),
+    SecondsOutOfRange(u32),
+    TooLarge,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Duration is empty."),
+            Self::InvalidNumber(number) => write!(f, "\"{}\" is not a number.", number),
+            Self::UnknownUnit(unit) => write!(f, "\"{}\" is not a unit, use h, m or s.", unit),
+            Self::MissingUnit(number) => write!(f, "\"{}\" has no unit, use h, m or s.", number),
+            Self::InvalidClockFormat(duration) => {
+                write!(f, "\"{}\" is not a duration like 1:30 or 1:30:00.", duration)
+            }
+            Self::MinutesOutOfRange(minutes) => {
+                write!(f, "{} minutes have to be written as hours.", minutes)
+            }
+            Self::SecondsOutOfRange(seconds) => {
+                write!(f, "{} seconds have to be written as minutes.", seconds)
+            }
+            Self::TooLarge => write!(f, "Duration is too large."),
+        }
+    }
+}
+
+// ------ parse ------
+
+/// Parses human durations like `1:30`, `1:30:00`, `1h30m`, `90m`, `1.5`, `1,5h` or `-0:15`.
+///
+/// Numbers without a unit are hours. Both `.` and `,` are accepted as the decimal separator.
+pub fn parse(duration: &str) -> Result<Duration, ParseError> {
+    let duration = duration.trim();
+    if duration.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let (negative, unsigned) = match duration.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned.trim_start()),
+        None => (false, duration),
+    };
+
+    let seconds = if unsigned.contains(':') {
+        parse_clock(unsigned)?
+    } else if unsigned.chars().any(char::is_alphabetic) {
+        parse_with_units(unsigned)?
+    } else {
+        parse_number(unsigned)? * 3600.
+    };
+
+    if seconds > MAX_SECONDS {
+        return Err(ParseError::TooLarge);
+    }
+    let seconds = seconds.round() as i64;
+    Ok(Duration::seconds(if negative { -seconds } else { seconds }))
+}
+
+/// `h:mm` or `h:mm:ss`.
+fn parse_clock(duration: &str) -> Result<f64, ParseError> {
+    let invalid = || ParseError::InvalidClockFormat(duration.to_owned());
+
+    let parts = duration
+        .split(':')
+        .map(|part| {
+            if part.is_empty() || not_digits(part) {
+                return Err(invalid());
+            }
+            part.parse::<u32>().map_err(|_| invalid())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [hours, minutes] => (*hours, *minutes, 0),
+        [hours, minutes, seconds] => (*hours, *minutes, *seconds),
+        _ => return Err(invalid()),
+    };
+    if minutes >= 60 {
+        return Err(ParseError::MinutesOutOfRange(minutes));
+    }
+    if seconds >= 60 {
+        return Err(ParseError::SecondsOutOfRange(seconds));
+    }
+    Ok(f64::from(hours) * 3600. + f64::from(minutes) * 60. + f64::from(seconds))
+}
+
+/// Sequence of numbers with units, e.g. `1h 30m` or `90m`.
+fn parse_with_units(duration: &str) -> Result<f64, ParseError> {
+    let mut seconds = 0.;
+    let mut rest = duration.trim_start();
+
+    while not(rest.is_empty()) {
+        let number_end = rest
+            .find(|character: char| not(is_number_char(character)))
+            .unwrap_or_else(|| rest.len());
+        let (number, after_number) = rest.split_at(number_end);
+        if number.is_empty() {
+            return Err(ParseError::InvalidNumber(
+                after_number.split_whitespace().next().unwrap_or_default().to_owned(),
+            ));
+        }
+
+        let after_number = after_number.trim_start();
+        let unit_end = after_number
+            .find(|character: char| not(character.is_alphabetic()))
+            .unwrap_or_else(|| after_number.len());
+        let (unit, after_unit) = after_number.split_at(unit_end);
+
+        let unit_seconds = match unit.to_lowercase().as_str() {
+            "" => return Err(ParseError::MissingUnit(number.to_owned())),
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3600.,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60.,
+            "s" | "sec" | "secs" | "second" | "seconds" => 1.,
+            _ => return Err(ParseError::UnknownUnit(unit.to_owned())),
+        };
+        seconds += parse_number(number)? * unit_seconds;
+        rest = after_unit.trim_start();
+    }
+    Ok(seconds)
+}
+
+/// Decimal number with `.` or `,` as the separator.
+fn parse_number(number: &str) -> Result<f64, ParseError> {
+    let invalid = || ParseError::InvalidNumber(number.to_owned());
+
+    let separators = number
+        .matches(|character: char| character == '.' || character == ',')
+        .count();
+    if separators > 1 || not(number.chars().all(is_number_char)) {
+        return Err(invalid());
+    }
+    number
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(invalid)
+}
+
+fn is_number_char(character: char) -> bool {
+    character.is_ascii_digit() || character == '.' || character == ','
+}
+
+fn not_digits(text: &str) -> bool {
+    text.chars().any(|character| not(character.is_ascii_digit()))
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn minutes(minutes: i64) -> Duration {
+        Duration::minutes(minutes)
+    }
+
+    #[wasm_bindgen_test]
+    fn clock() {
+        assert_eq!(parse("1:30"), Ok(minutes(90)));
+        assert_eq!(parse("1:30:15"), Ok(minutes(90) + Duration::seconds(15)));
+        assert_eq!(parse(" 0:05 "), Ok(minutes(5)));
+    }
+
+    #[wasm_bindgen_test]
+    fn negative() {
+        assert_eq!(parse("-0:15"), Ok(minutes(-15)));
+        assert_eq!(parse("-1.5"), Ok(minutes(-90)));
+    }
+
+    #[wasm_bindgen_test]
+    fn units() {
+        assert_eq!(parse("1h30m"), Ok(minutes(90)));
+        assert_eq!(parse("1h 30m"), Ok(minutes(90)));
+        assert_eq!(parse("90m"), Ok(minutes(90)));
+        assert_eq!(parse("2 hours 5 min"), Ok(minutes(125)));
+        assert_eq!(parse("45s"), Ok(Duration::seconds(45)));
+    }
+
+    #[wasm_bindgen_test]
+    fn decimal_hours() {
+        assert_eq!(parse("1.5"), Ok(minutes(90)));
+        assert_eq!(parse("1,5"), Ok(minutes(90)));
+        assert_eq!(parse("1,5h"), Ok(minutes(90)));
+        assert_eq!(parse("0.25h"), Ok(minutes(15)));
+    }
+
+    #[wasm_bindgen_test]
+    fn errors() {
+        assert_eq!(parse("  "), Err(ParseError::Empty));
+        assert_eq!(parse("abc"), Err(ParseError::InvalidNumber("abc".to_owned())));
+        assert_eq!(parse("1.2.3"), Err(ParseError::InvalidNumber("1.2.3".to_owned())));
+        assert_eq!(parse("3d"), Err(ParseError::UnknownUnit("d".to_owned())));
+        assert_eq!(parse("1h30"), Err(ParseError::MissingUnit("30".to_owned())));
+        assert_eq!(parse("1:75"), Err(ParseError::MinutesOutOfRange(75)));
+        assert_eq!(parse("1:30:60"), Err(ParseError::SecondsOutOfRange(60)));
+        assert_eq!(
+            parse("1:2:3:4"),
+            Err(ParseError::InvalidClockFormat("1:2:3:4".to_owned()))
+        );
+        assert_eq!(parse("1000000h"), Err(ParseError::TooLarge));
+    }
+}
@@ -0,0 +1,156 @@
+use seed::{prelude::*, *};
+
+use crate::graphql::{GraphQLError, Replay};
+
+// ------ Notice ------
+
+/// An error shown in the error center.
+pub trait Notice {
+    /// Readable message for users.
+    fn summary(&self) -> String;
+
+    /// Technical details, hidden until expanded.
+    fn details(&self) -> Option<String>;
+
+    fn can_retry(&self) -> bool {
+        false
+    }
+}
+
+// ------ RequestError ------
+
+/// A failed request, it can be sent again if it has a `replay`.
+#[derive(Debug)]
+pub struct RequestError {
+    pub error: GraphQLError,
+    pub replay: Option<Replay>,
+}
+
+impl Notice for GraphQLError {
+    fn summary(&self) -> String {
+        self.to_string()
+    }
+
+    fn details(&self) -> Option<String> {
+        Some(GraphQLError::details(self))
+    }
+}
+
+impl From<GraphQLError> for RequestError {
+    fn from(error: GraphQLError) -> Self {
+        Self { error, replay: None }
+    }
+}
+
+impl Notice for RequestError {
+    fn summary(&self) -> String {
+        self.error.summary()
+    }
+
+    fn details(&self) -> Option<String> {
+        Notice::details(&self.error)
+    }
+
+    fn can_retry(&self) -> bool {
+        self.replay.is_some()
+    }
+}
+
+// ------ Groups ------
+
+/// Whether the errors would be shown as the same notification.
+fn same_group(a: &impl Notice, b: &impl Notice) -> bool {
+    a.summary() == b.summary() && a.details() == b.details()
+}
+
+/// Removes the error at `index` with all its duplicates.
+pub fn remove_group<E: Notice>(errors: &mut Vec<E>, index: usize) -> Vec<E> {
+    if index >= errors.len() {
+        return Vec::new();
+    }
+    let error = errors.remove(index);
+    let (mut removed, kept): (Vec<_>, Vec<_>) = errors
+        .drain(..)
+        .partition(|other_error| same_group(other_error, &error));
+    *errors = kept;
+    removed.insert(0, error);
+    removed
+}
+
+// ------ view ------
+
+/// Notifications of the errors, duplicates are grouped.
+///
+/// Messages are created with the index of the first error in the group,
+/// `on_retry` is used only for errors that can be retried.
+pub fn view<E: Notice, Ms: 'static>(
+    errors: &[E],
+    on_dismiss: fn(usize) -> Ms,
+    on_retry: Option<fn(usize) -> Ms>,
+    on_dismiss_all: fn() -> Ms,
+) -> Node<Ms> {
+    if errors.is_empty() {
+        return empty![];
+    }
+
+    // (index of the first error, count)
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for (index, error) in errors.iter().enumerate() {
+        match groups
+            .iter_mut()
+            .find(|(first_index, _)| same_group(&errors[*first_index], error))
+        {
+            Some((_, count)) => *count += 1,
+            None => groups.push((index, 1)),
+        }
+    }
+
+    div![
+        groups.into_iter().map(|(index, count)| {
+            view_notification(&errors[index], index, count, on_dismiss, on_retry)
+        }),
+        IF!(errors.len() > 1 => div![
+            C!["has-text-right"],
+            button![
+                C!["button", "is-small"],
+                ev(Ev::Click, move |_| on_dismiss_all()),
+                "Dismiss all",
+            ]
+        ]),
+    ]
+}
+
+fn view_notification<Ms: 'static>(
+    error: &impl Notice,
+    index: usize,
+    count: usize,
+    on_dismiss: fn(usize) -> Ms,
+    on_retry: Option<fn(usize) -> Ms>,
+) -> Node<Ms> {
+    div![
+        C!["notification", "is-danger", "mb-3"],
+        button![C!["delete"], ev(Ev::Click, move |_| on_dismiss(index))],
+        p![
+            error.summary(),
+            IF!(count > 1 => span![C!["tag", "is-light", "ml-2"], format!("×{}", count)]),
+        ],
+        error.details().map(|error_details| {
+            details![
+                C!["mt-2"],
+                summary!["Details"],
+                pre![
+                    C!["has-text-dark", "is-size-7"],
+                    style! {St::MaxHeight => rem(12), St::Overflow => "auto"},
+                    error_details
+                ],
+            ]
+        }),
+        on_retry.filter(|_| error.can_retry()).map(|on_retry| {
+            button![
+                C!["button", "is-small", "is-light", "mt-2"],
+                ev(Ev::Click, move |_| on_retry(index)),
+                "Retry",
+            ]
+        }),
+    ]
+}
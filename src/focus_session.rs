@@ -0,0 +1,76 @@
+use chrono::prelude::*;
+use ulid::Ulid;
+
+use crate::preferences::PomodoroSettings;
+
+// ------ FocusSession ------
+
+/// Pomodoro cycle that tracks the work intervals in one project.
+///
+/// It's kept by the app so it keeps running on every page.
+pub struct FocusSession {
+    pub client_id: Ulid,
+    pub project_id: Ulid,
+    pub phase: FocusPhase,
+    pub phase_ends: DateTime<Local>,
+    pub completed_work_intervals: u32,
+}
+
+impl FocusSession {
+    pub fn new(
+        client_id: Ulid,
+        project_id: Ulid,
+        now: DateTime<Local>,
+        settings: &PomodoroSettings,
+    ) -> Self {
+        Self {
+            client_id,
+            project_id,
+            phase: FocusPhase::Work,
+            phase_ends: now + settings.work(),
+            completed_work_intervals: 0,
+        }
+    }
+
+    /// Moves to the next phase, every n-th work interval is followed by a long break.
+    pub fn advance(&mut self, now: DateTime<Local>, settings: &PomodoroSettings) {
+        self.phase = match self.phase {
+            FocusPhase::Work => {
+                self.completed_work_intervals += 1;
+                if self.completed_work_intervals % settings.work_intervals_before_long_break.max(1)
+                    == 0
+                {
+                    FocusPhase::LongBreak
+                } else {
+                    FocusPhase::ShortBreak
+                }
+            }
+            FocusPhase::ShortBreak | FocusPhase::LongBreak => FocusPhase::Work,
+        };
+        self.phase_ends = now
+            + match self.phase {
+                FocusPhase::Work => settings.work(),
+                FocusPhase::ShortBreak => settings.short_break(),
+                FocusPhase::LongBreak => settings.long_break(),
+            };
+    }
+}
+
+// ------ FocusPhase ------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FocusPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl FocusPhase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Work => "Work",
+            Self::ShortBreak => "Short break",
+            Self::LongBreak => "Long break",
+        }
+    }
+}
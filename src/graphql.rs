@@ -1,17 +1,72 @@
 use seed::{prelude::*};
+use serde::Deserialize;
 
 use cynic;
 
+use std::collections::BTreeMap;
+use std::fmt;
+
 pub type Result<T> = std::result::Result<T, GraphQLError>;
 
+// ------ Config ------
+
+/// Loaded from `graphql_config.json` at startup.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    pub endpoint: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Header for the Auth0 access token, `Authorization` sends it as a bearer token.
+    #[serde(default = "default_token_header")]
+    pub token_header: String,
+    /// Owner of the queried and created clients, set from the logged-in user.
+    ///
+    /// Dgraph enforces the ownership by `@auth` rules in `dgraph_schema.graphql`,
+    /// the filter alone doesn't protect the data of other users.
+    #[serde(skip)]
+    pub user_id: Option<String>,
+}
+
+fn default_token_header() -> String {
+    "X-Auth-Token".to_owned()
+}
+
+impl Config {
+    pub fn with_auth(mut self, token: Option<&str>, user_id: Option<&str>) -> Self {
+        if let Some(token) = token {
+            let value = if self.token_header.eq_ignore_ascii_case("Authorization") {
+                format!("Bearer {}", token)
+            } else {
+                token.to_owned()
+            };
+            self.headers.insert(self.token_header.clone(), value);
+        }
+        self.user_id = user_id.map(ToOwned::to_owned);
+        self
+    }
+
+    pub fn user_id(&self) -> String {
+        self.user_id.clone().unwrap_or_default()
+    }
+}
+
+fn build_request<'a, ResponseData: 'a>(
+    config: &Config,
+    operation: &cynic::Operation<'a, ResponseData>
+) -> Result<Request<'static>> {
+    let request = config.headers.iter().fold(
+        Request::new(config.endpoint.clone()).method(Method::Post),
+        |request, (name, value)| request.header(Header::custom(name.clone(), value.clone()))
+    );
+    Ok(request.json(operation)?)
+}
+
 pub async fn send_operation<'a, ResponseData: 'a>(
+    config: &Config,
     operation: cynic::Operation<'a, ResponseData>
 ) -> Result<ResponseData> {
     let graphql_response = 
-        // @TODO: Move url to a config file.
-        Request::new("https://time-tracker.eu-central-1.aws.cloud.dgraph.io/graphql")
-            .method(Method::Post)
-            .json(&operation)?
+        build_request(config, &operation)?
             .fetch()
             .await?
             .check_status()?
@@ -22,7 +77,40 @@ pub async fn send_operation<'a, ResponseData: 'a>(
     if let Some(errors) = response_data.errors {
         Err(errors)?
     }
-    Ok(response_data.data.expect("response data"))
+    response_data.data.ok_or_else(|| GraphQLError::missing_data("data"))
+}
+
+// ------ Replay ------
+
+/// An already built request of an operation, it can be sent again when it has failed.
+///
+/// The response data are ignored, so only mutations whose results aren't needed should be replayed.
+#[derive(Debug, Clone)]
+pub struct Replay(Request<'static>);
+
+impl Replay {
+    pub fn new<'a, ResponseData: 'a>(
+        config: &Config,
+        operation: &cynic::Operation<'a, ResponseData>
+    ) -> Result<Self> {
+        build_request(config, operation).map(Self)
+    }
+
+    pub async fn send(&self) -> Result<()> {
+        let graphql_response: cynic::GraphQLResponse<serde::de::IgnoredAny> = 
+            self.0
+                .clone()
+                .fetch()
+                .await?
+                .check_status()?
+                .json()
+                .await?;
+
+        if let Some(errors) = graphql_response.errors {
+            Err(errors)?
+        }
+        Ok(())
+    }
 }
 
 // ------ Error ------
@@ -31,7 +119,14 @@ pub async fn send_operation<'a, ResponseData: 'a>(
 pub enum GraphQLError {
     FetchError(FetchError),
     ResponseErrors(Vec<cynic::GraphQLError>),
-    DecodeError(cynic::DecodeError)
+    DecodeError(cynic::DecodeError),
+    /// A record in the response can't be mapped to an entity, e.g. because of an invalid id.
+    InvalidData {
+        entity: &'static str,
+        id: String,
+        field: &'static str,
+        reason: String,
+    },
 }
 
 impl From<FetchError> for GraphQLError {
@@ -52,45 +147,165 @@ impl From<cynic::DecodeError> for GraphQLError {
     }
 }
 
+impl GraphQLError {
+    /// An expected part of the response, e.g. `data`, is missing.
+    pub fn missing_data(field: &'static str) -> Self {
+        Self::InvalidData {
+            entity: "response",
+            id: String::new(),
+            field,
+            reason: "missing".to_owned(),
+        }
+    }
+
+    /// Technical details for bug reports, the `Display` output is meant for users.
+    pub fn details(&self) -> String {
+        match self {
+            Self::FetchError(fetch_error) => format!("{:#?}", fetch_error),
+            Self::ResponseErrors(response_errors) => format!("{:#?}", response_errors),
+            Self::DecodeError(decode_error) => format!("{:#?}", decode_error),
+            Self::InvalidData { entity, id, field, reason } => {
+                format!("{} {:?}, field `{}`: {}", entity, id, field, reason)
+            }
+        }
+    }
+}
+
+impl fmt::Display for GraphQLError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::FetchError(FetchError::NetworkError(_)) => {
+                write!(f, "The server is unreachable, please check your connection.")
+            }
+            Self::FetchError(FetchError::StatusError(status)) => {
+                write!(f, "The server responded with {} {}.", status.code, status.text)
+            }
+            Self::FetchError(_) => write!(f, "The request could not be sent."),
+            Self::ResponseErrors(response_errors) => {
+                let messages = response_errors
+                    .iter()
+                    .map(|response_error| response_error.message.as_str())
+                    .collect::<Vec<_>>();
+                write!(f, "The server rejected the request: {}", messages.join(", "))
+            }
+            Self::DecodeError(_) => write!(f, "The server sent an unexpected response."),
+            Self::InvalidData { entity, field, .. } => {
+                write!(f, "A {} with an invalid {} has been skipped.", entity.replace('_', " "), field)
+            }
+        }
+    }
+}
+
 pub mod queries {
     #[cynic::query_module(
         schema_path = "schema.graphql",
         query_module = "query_dsl",
     )]
-    pub mod clients_with_projects {
+    pub mod client_with_descendant_ids {
         use crate::graphql::query_dsl;
 
         ///```graphql
-        ///{
-        ///    queryClient {
-        ///        id
-        ///        name
+        ///query($id: String!) {
+        ///    getClient(id: $id) {
         ///        projects {
         ///            id
-        ///            name
+        ///            time_entries {
+        ///                id
+        ///            }
+        ///        }
+        ///        time_blocks {
+        ///            id
+        ///            invoice {
+        ///                id
+        ///            }
         ///        }
         ///    }
         ///}
         ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub id: String,
+        }
+
         #[derive(cynic::QueryFragment, Debug)]
-        #[cynic(graphql_type = "Query")]
+        #[cynic(graphql_type = "Query", argument_struct = "Arguments")]
         pub struct Query {
-            pub query_client: Option<Vec<Option<Client>>>,
+            #[arguments(id = args.id.clone())]
+            pub get_client: Option<Client>,
         }
 
         #[derive(cynic::QueryFragment, Debug)]
         #[cynic(graphql_type = "Client")]
         pub struct Client {
-            pub id: String,
-            pub name: String,
             pub projects: Vec<Project>,
+            pub time_blocks: Vec<TimeBlock>,
         }
 
         #[derive(cynic::QueryFragment, Debug)]
         #[cynic(graphql_type = "Project")]
         pub struct Project {
             pub id: String,
-            pub name: String,
+            pub time_entries: Vec<TimeEntry>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "TimeEntry")]
+        pub struct TimeEntry {
+            pub id: String,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "TimeBlock")]
+        pub struct TimeBlock {
+            pub id: String,
+            pub invoice: Option<Invoice>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Invoice")]
+        pub struct Invoice {
+            pub id: String,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod project_with_descendant_ids {
+        use crate::graphql::query_dsl;
+
+        ///```graphql
+        ///query($id: String!) {
+        ///    getProject(id: $id) {
+        ///        time_entries {
+        ///            id
+        ///        }
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub id: String,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Query", argument_struct = "Arguments")]
+        pub struct Query {
+            #[arguments(id = args.id.clone())]
+            pub get_project: Option<Project>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Project")]
+        pub struct Project {
+            pub time_entries: Vec<TimeEntry>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "TimeEntry")]
+        pub struct TimeEntry {
+            pub id: String,
         }
     }
 
@@ -98,30 +313,37 @@ pub mod queries {
         schema_path = "schema.graphql",
         query_module = "query_dsl",
     )]
-    pub mod clients_with_projects_with_time_entries {
+    pub mod clients_with_descendant_ids {
         use crate::graphql::{query_dsl, types::*};
 
         ///```graphql
-        ///{
-        ///    queryClient {
+        ///query($filter: ClientFilter) {
+        ///    queryClient(filter: $filter) {
         ///        id
-        ///        name
         ///        projects {
         ///            id
-        ///            name
         ///            time_entries {
         ///                id
-        ///                name
-        ///                started
-        ///                stopped
+        ///            }
+        ///        }
+        ///        time_blocks {
+        ///            id
+        ///            invoice {
+        ///                id
         ///            }
         ///        }
         ///    }
         ///}
         ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub filter: ClientFilter,
+        }
+
         #[derive(cynic::QueryFragment, Debug)]
-        #[cynic(graphql_type = "Query")]
+        #[cynic(graphql_type = "Query", argument_struct = "Arguments")]
         pub struct Query {
+            #[arguments(filter = args.filter.clone())]
             pub query_client: Option<Vec<Option<Client>>>,
         }
 
@@ -129,15 +351,14 @@ pub mod queries {
         #[cynic(graphql_type = "Client")]
         pub struct Client {
             pub id: String,
-            pub name: String,
             pub projects: Vec<Project>,
+            pub time_blocks: Vec<TimeBlock>,
         }
 
         #[derive(cynic::QueryFragment, Debug)]
         #[cynic(graphql_type = "Project")]
         pub struct Project {
             pub id: String,
-            pub name: String,
             pub time_entries: Vec<TimeEntry>,
         }
 
@@ -145,9 +366,19 @@ pub mod queries {
         #[cynic(graphql_type = "TimeEntry")]
         pub struct TimeEntry {
             pub id: String,
-            pub name: String,
-            pub started: DateTime,
-            pub stopped: Option<DateTime>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "TimeBlock")]
+        pub struct TimeBlock {
+            pub id: String,
+            pub invoice: Option<Invoice>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Invoice")]
+        pub struct Invoice {
+            pub id: String,
         }
     }
 
@@ -155,14 +386,24 @@ pub mod queries {
         schema_path = "schema.graphql",
         query_module = "query_dsl",
     )]
-    pub mod clients_with_time_blocks_and_time_entries {
+    pub mod clients_with_all_descendants {
         use crate::graphql::{query_dsl, types::*};
 
         ///```graphql
-        ///{
-        ///    queryClient {
+        ///query($filter: ClientFilter) {
+        ///    queryClient(filter: $filter) {
         ///        id
         ///        name
+        ///        projects {
+        ///            id
+        ///            name
+        ///            time_entries {
+        ///                id
+        ///                name
+        ///                started
+        ///                stopped
+        ///            }
+        ///        }
         ///        time_blocks {
         ///            id
         ///            name
@@ -174,18 +415,18 @@ pub mod queries {
         ///                url
         ///            }
         ///        }
-        ///        projects {
-        ///            time_entries {
-        ///                started
-        ///                stopped
-        ///            }
-        ///        }
         ///    }
         ///}
         ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub filter: ClientFilter,
+        }
+
         #[derive(cynic::QueryFragment, Debug)]
-        #[cynic(graphql_type = "Query")]
+        #[cynic(graphql_type = "Query", argument_struct = "Arguments")]
         pub struct Query {
+            #[arguments(filter = args.filter.clone())]
             pub query_client: Option<Vec<Option<Client>>>,
         }
 
@@ -194,8 +435,25 @@ pub mod queries {
         pub struct Client {
             pub id: String,
             pub name: String,
-            pub time_blocks: Vec<TimeBlock>,
             pub projects: Vec<Project>,
+            pub time_blocks: Vec<TimeBlock>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Project")]
+        pub struct Project {
+            pub id: String,
+            pub name: String,
+            pub time_entries: Vec<TimeEntry>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "TimeEntry")]
+        pub struct TimeEntry {
+            pub id: String,
+            pub name: String,
+            pub started: DateTime,
+            pub stopped: Option<DateTime>,
         }
 
         #[derive(cynic::QueryFragment, Debug)]
@@ -208,14 +466,6 @@ pub mod queries {
             pub invoice: Option<Invoice>,
         }
 
-        #[derive(cynic::Enum, Debug, Copy, Clone)]
-        #[cynic(graphql_type = "TimeBlockStatus", rename_all = "SCREAMING_SNAKE_CASE")]
-        pub enum TimeBlockStatus {
-            NonBillable,
-            Unpaid,
-            Paid,
-        }
-
         #[derive(cynic::QueryFragment, Debug)]
         #[cynic(graphql_type = "Invoice")]
         pub struct Invoice {
@@ -223,25 +473,1101 @@ pub mod queries {
             pub custom_id: Option<String>,
             pub url: Option<String>,
         }
+    }
+}
+
+pub mod mutations {
+    use cynic::{
+        selection_set::{self, SelectionSet},
+        FragmentContext, QueryFragment,
+    };
+
+    use super::{query_dsl, types::*};
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod add_client {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: [AddClientInput!]!) {
+        ///    addClient(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: Vec<AddClientInput>,
+        }
 
         #[derive(cynic::QueryFragment, Debug)]
-        #[cynic(graphql_type = "Project")]
-        pub struct Project {
-            pub time_entries: Vec<TimeEntry>,
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub add_client: Option<AddClientPayload>,
         }
 
         #[derive(cynic::QueryFragment, Debug)]
-        #[cynic(graphql_type = "TimeEntry")]
-        pub struct TimeEntry {
-            pub started: DateTime,
-            pub stopped: Option<DateTime>,
+        #[cynic(graphql_type = "AddClientPayload")]
+        pub struct AddClientPayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone)]
+        #[cynic(graphql_type = "AddClientInput", rename_all = "None")]
+        pub struct AddClientInput {
+            pub id: String,
+            pub name: String,
+            pub projects: Vec<ProjectRef>,
+            pub time_blocks: Vec<TimeBlockRef>,
+            pub user: String,
         }
     }
-}
 
-mod types {
-    #[derive(cynic::Scalar, Debug)]
-    pub struct DateTime(pub String);
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod update_client {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: UpdateClientInput!) {
+        ///    updateClient(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: UpdateClientInput,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub update_client: Option<UpdateClientPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "UpdateClientPayload")]
+        pub struct UpdateClientPayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone)]
+        #[cynic(graphql_type = "UpdateClientInput", rename_all = "None")]
+        pub struct UpdateClientInput {
+            pub filter: ClientFilter,
+            pub set: Option<ClientPatch>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone, Default)]
+        #[cynic(graphql_type = "ClientPatch", rename_all = "None")]
+        pub struct ClientPatch {
+            #[cynic(skip_serializing_if = "Option::is_none")]
+            pub name: Option<String>,
+        }
+
+        impl UpdateClientInput {
+            pub fn new(id: impl ToString, set: ClientPatch) -> Self {
+                Self {
+                    filter: ClientFilter::id(id),
+                    set: Some(set),
+                }
+            }
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod delete_clients {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($filter: ClientFilter!) {
+        ///    deleteClient(filter: $filter) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub filter: ClientFilter,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(filter = args.filter.clone())]
+            pub delete_client: Option<DeleteClientPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "DeleteClientPayload")]
+        pub struct DeleteClientPayload {
+            pub num_uids: Option<i32>,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod add_project {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: [AddProjectInput!]!) {
+        ///    addProject(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: Vec<AddProjectInput>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub add_project: Option<AddProjectPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "AddProjectPayload")]
+        pub struct AddProjectPayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone)]
+        #[cynic(graphql_type = "AddProjectInput", rename_all = "None")]
+        pub struct AddProjectInput {
+            pub id: String,
+            pub name: String,
+            pub time_entries: Vec<TimeEntryRef>,
+            pub client: ClientRef,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod update_project {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: UpdateProjectInput!) {
+        ///    updateProject(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: UpdateProjectInput,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub update_project: Option<UpdateProjectPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "UpdateProjectPayload")]
+        pub struct UpdateProjectPayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone)]
+        #[cynic(graphql_type = "UpdateProjectInput", rename_all = "None")]
+        pub struct UpdateProjectInput {
+            pub filter: ProjectFilter,
+            pub set: Option<ProjectPatch>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone, Default)]
+        #[cynic(graphql_type = "ProjectPatch", rename_all = "None")]
+        pub struct ProjectPatch {
+            #[cynic(skip_serializing_if = "Option::is_none")]
+            pub name: Option<String>,
+        }
+
+        impl UpdateProjectInput {
+            pub fn new(id: impl ToString, set: ProjectPatch) -> Self {
+                Self {
+                    filter: ProjectFilter::id(id),
+                    set: Some(set),
+                }
+            }
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod delete_projects {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($filter: ProjectFilter!) {
+        ///    deleteProject(filter: $filter) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub filter: ProjectFilter,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(filter = args.filter.clone())]
+            pub delete_project: Option<DeleteProjectPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "DeleteProjectPayload")]
+        pub struct DeleteProjectPayload {
+            pub num_uids: Option<i32>,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod delete_time_entries {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($filter: TimeEntryFilter!) {
+        ///    deleteTimeEntry(filter: $filter) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub filter: TimeEntryFilter,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(filter = args.filter.clone())]
+            pub delete_time_entry: Option<DeleteTimeEntryPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "DeleteTimeEntryPayload")]
+        pub struct DeleteTimeEntryPayload {
+            pub num_uids: Option<i32>,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod add_time_block {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: [AddTimeBlockInput!]!) {
+        ///    addTimeBlock(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: Vec<AddTimeBlockInput>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub add_time_block: Option<AddTimeBlockPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "AddTimeBlockPayload")]
+        pub struct AddTimeBlockPayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone)]
+        #[cynic(graphql_type = "AddTimeBlockInput", rename_all = "None")]
+        pub struct AddTimeBlockInput {
+            pub id: String,
+            pub name: String,
+            pub status: TimeBlockStatus,
+            pub duration: i32,
+            pub client: ClientRef,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod update_time_block {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: UpdateTimeBlockInput!) {
+        ///    updateTimeBlock(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: UpdateTimeBlockInput,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub update_time_block: Option<UpdateTimeBlockPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "UpdateTimeBlockPayload")]
+        pub struct UpdateTimeBlockPayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone)]
+        #[cynic(graphql_type = "UpdateTimeBlockInput", rename_all = "None")]
+        pub struct UpdateTimeBlockInput {
+            pub filter: TimeBlockFilter,
+            pub set: Option<TimeBlockPatch>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone, Default)]
+        #[cynic(graphql_type = "TimeBlockPatch", rename_all = "None")]
+        pub struct TimeBlockPatch {
+            #[cynic(skip_serializing_if = "Option::is_none")]
+            pub name: Option<String>,
+            #[cynic(skip_serializing_if = "Option::is_none")]
+            pub status: Option<TimeBlockStatus>,
+            #[cynic(skip_serializing_if = "Option::is_none")]
+            pub duration: Option<i32>,
+        }
+
+        impl UpdateTimeBlockInput {
+            pub fn new(id: impl ToString, set: TimeBlockPatch) -> Self {
+                Self {
+                    filter: TimeBlockFilter::id(id),
+                    set: Some(set),
+                }
+            }
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod delete_time_blocks {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($filter: TimeBlockFilter!) {
+        ///    deleteTimeBlock(filter: $filter) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub filter: TimeBlockFilter,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(filter = args.filter.clone())]
+            pub delete_time_block: Option<DeleteTimeBlockPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "DeleteTimeBlockPayload")]
+        pub struct DeleteTimeBlockPayload {
+            pub num_uids: Option<i32>,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod add_invoice {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: [AddInvoiceInput!]!) {
+        ///    addInvoice(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: Vec<AddInvoiceInput>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub add_invoice: Option<AddInvoicePayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "AddInvoicePayload")]
+        pub struct AddInvoicePayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone)]
+        #[cynic(graphql_type = "AddInvoiceInput", rename_all = "None")]
+        pub struct AddInvoiceInput {
+            pub id: String,
+            #[cynic(skip_serializing_if = "Option::is_none")]
+            pub custom_id: Option<String>,
+            #[cynic(skip_serializing_if = "Option::is_none")]
+            pub url: Option<String>,
+            pub time_block: TimeBlockRef,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod update_invoice {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: UpdateInvoiceInput!) {
+        ///    updateInvoice(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: UpdateInvoiceInput,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub update_invoice: Option<UpdateInvoicePayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "UpdateInvoicePayload")]
+        pub struct UpdateInvoicePayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone)]
+        #[cynic(graphql_type = "UpdateInvoiceInput", rename_all = "None")]
+        pub struct UpdateInvoiceInput {
+            pub filter: InvoiceFilter,
+            pub set: Option<InvoicePatch>,
+        }
+
+        #[derive(cynic::InputObject, Debug, Clone, Default)]
+        #[cynic(graphql_type = "InvoicePatch", rename_all = "None")]
+        pub struct InvoicePatch {
+            #[cynic(skip_serializing_if = "Option::is_none")]
+            pub custom_id: Option<String>,
+            #[cynic(skip_serializing_if = "Option::is_none")]
+            pub url: Option<String>,
+        }
+
+        impl UpdateInvoiceInput {
+            pub fn new(id: impl ToString, set: InvoicePatch) -> Self {
+                Self {
+                    filter: InvoiceFilter::id(id),
+                    set: Some(set),
+                }
+            }
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod delete_invoices {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($filter: InvoiceFilter!) {
+        ///    deleteInvoice(filter: $filter) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub filter: InvoiceFilter,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(filter = args.filter.clone())]
+            pub delete_invoice: Option<DeleteInvoicePayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "DeleteInvoicePayload")]
+        pub struct DeleteInvoicePayload {
+            pub num_uids: Option<i32>,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod add_time_entry {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: [AddTimeEntryInput!]!) {
+        ///    addTimeEntry(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: Vec<AddTimeEntryInput>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub add_time_entry: Option<AddTimeEntryPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "AddTimeEntryPayload")]
+        pub struct AddTimeEntryPayload {
+            pub num_uids: Option<i32>,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod update_time_entry {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: UpdateTimeEntryInput!) {
+        ///    updateTimeEntry(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: UpdateTimeEntryInput,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub update_time_entry: Option<UpdateTimeEntryPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "UpdateTimeEntryPayload")]
+        pub struct UpdateTimeEntryPayload {
+            pub num_uids: Option<i32>,
+        }
+    }
+
+    /// Stops time entries and adds new ones in one request, e.g. starting a timer or splitting.
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod stop_and_add_time_entry {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($stop: UpdateTimeEntryInput!, $input: [AddTimeEntryInput!]!) {
+        ///    updateTimeEntry(input: $stop) {
+        ///        numUids
+        ///    }
+        ///    addTimeEntry(input: $input) {
+        ///        numUids
+        ///    }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub stop: UpdateTimeEntryInput,
+            pub input: Vec<AddTimeEntryInput>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.stop.clone())]
+            pub update_time_entry: Option<UpdateTimeEntryPayload>,
+            #[arguments(input = args.input.clone())]
+            pub add_time_entry: Option<AddTimeEntryPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "UpdateTimeEntryPayload")]
+        pub struct UpdateTimeEntryPayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "AddTimeEntryPayload")]
+        pub struct AddTimeEntryPayload {
+            pub num_uids: Option<i32>,
+        }
+    }
+
+    #[cynic::query_module(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+    )]
+    pub mod merge_time_entries {
+        use crate::graphql::{query_dsl, types::*};
+
+        ///```graphql
+        ///mutation($input: UpdateTimeEntryInput!, $time_entry: TimeEntryFilter!) {
+        ///    updateTimeEntry(input: $input) { numUids }
+        ///    deleteTimeEntry(filter: $time_entry) { numUids }
+        ///}
+        ///```
+        #[derive(cynic::FragmentArguments, Debug)]
+        pub struct Arguments {
+            pub input: UpdateTimeEntryInput,
+            pub time_entry: TimeEntryFilter,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "Mutation", argument_struct = "Arguments")]
+        pub struct Mutation {
+            #[arguments(input = args.input.clone())]
+            pub update_time_entry: Option<UpdateTimeEntryPayload>,
+            #[arguments(filter = args.time_entry.clone())]
+            pub delete_time_entry: Option<DeleteTimeEntryPayload>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "UpdateTimeEntryPayload")]
+        pub struct UpdateTimeEntryPayload {
+            pub num_uids: Option<i32>,
+        }
+
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(graphql_type = "DeleteTimeEntryPayload")]
+        pub struct DeleteTimeEntryPayload {
+            pub num_uids: Option<i32>,
+        }
+    }
+
+    // ------ Deletes ------
+
+    /// Ids of the entities deleted together, e.g. a client with its descendants.
+    ///
+    /// Dgraph doesn't cascade deletes, so the descendants are deleted explicitly.
+    #[derive(Debug, Default)]
+    pub struct Deletes {
+        pub invoice_ids: Vec<String>,
+        pub time_block_ids: Vec<String>,
+        pub time_entry_ids: Vec<String>,
+        pub project_ids: Vec<String>,
+        pub client_ids: Vec<String>,
+    }
+
+    impl Deletes {
+        /// Builds one mutation with a `delete*` field for each entity type, the descendants first,
+        /// so the whole cascade is sent, and fails or is retried, as one request.
+        ///
+        /// Types without ids are left out, so no filter with an empty `in` list is sent.
+        /// `None` when there is nothing to delete.
+        pub fn mutation(self) -> Option<cynic::Operation<'static, ()>> {
+            let mut fields = Vec::new();
+            if let Some(filter) = InvoiceFilter::any_of(self.invoice_ids) {
+                let arguments = delete_invoices::Arguments { filter };
+                fields.push(field::<delete_invoices::Mutation>(arguments));
+            }
+            if let Some(filter) = TimeBlockFilter::any_of(self.time_block_ids) {
+                let arguments = delete_time_blocks::Arguments { filter };
+                fields.push(field::<delete_time_blocks::Mutation>(arguments));
+            }
+            if let Some(filter) = TimeEntryFilter::any_of(self.time_entry_ids) {
+                let arguments = delete_time_entries::Arguments { filter };
+                fields.push(field::<delete_time_entries::Mutation>(arguments));
+            }
+            if let Some(filter) = ProjectFilter::any_of(self.project_ids) {
+                let arguments = delete_projects::Arguments { filter };
+                fields.push(field::<delete_projects::Mutation>(arguments));
+            }
+            if let Some(filter) = ClientFilter::any_of(self.client_ids) {
+                let arguments = delete_clients::Arguments { filter };
+                fields.push(field::<delete_clients::Mutation>(arguments));
+            }
+            let selection_set = fields
+                .into_iter()
+                .reduce(|fields, field| selection_set::map2(|(), ()| (), fields, field))?;
+            Some(cynic::Operation::mutation(selection_set))
+        }
+    }
+
+    /// Selection of the `delete*` field of a single-field mutation module, its response is ignored.
+    fn field<Fragment>(
+        arguments: Fragment::Arguments,
+    ) -> SelectionSet<'static, (), query_dsl::Mutation>
+    where
+        Fragment: QueryFragment<SelectionSet = SelectionSet<'static, Fragment, query_dsl::Mutation>>
+            + 'static,
+    {
+        Fragment::fragment(FragmentContext::new(&arguments)).map(|_| ())
+    }
+}
+
+pub mod types {
+    use super::query_dsl;
+
+    #[derive(cynic::Scalar, Debug, Clone)]
+    pub struct DateTime(pub String);
+
+    // ------ Filters ------
+
+    #[derive(cynic::InputObject, Debug, Clone, Default)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "StringHashFilter",
+        rename_all = "None"
+    )]
+    pub struct StringHashFilter {
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub eq: Option<String>,
+        #[cynic(rename = "in", skip_serializing_if = "Option::is_none")]
+        pub in_: Option<Vec<Option<String>>>,
+    }
+
+    impl StringHashFilter {
+        pub fn eq(value: impl ToString) -> Self {
+            Self {
+                eq: Some(value.to_string()),
+                ..Default::default()
+            }
+        }
+
+        /// `None` when there are no values, so no empty `in` list is sent to Dgraph.
+        ///
+        /// The `any_of` filters are `None` then as well, an empty filter would match all entities.
+        pub fn any_of(values: impl IntoIterator<Item = String>) -> Option<Self> {
+            let values = values.into_iter().map(Some).collect::<Vec<_>>();
+            if values.is_empty() {
+                return None;
+            }
+            Some(Self {
+                in_: Some(values),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone, Default)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "ClientFilter",
+        rename_all = "None"
+    )]
+    pub struct ClientFilter {
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub id: Option<StringHashFilter>,
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub user: Option<StringHashFilter>,
+    }
+
+    impl ClientFilter {
+        pub fn id(id: impl ToString) -> Self {
+            Self {
+                id: Some(StringHashFilter::eq(id)),
+                ..Default::default()
+            }
+        }
+
+        pub fn any_of(ids: impl IntoIterator<Item = String>) -> Option<Self> {
+            Some(Self {
+                id: Some(StringHashFilter::any_of(ids)?),
+                ..Default::default()
+            })
+        }
+
+        pub fn user(user_id: String) -> Self {
+            Self {
+                user: Some(StringHashFilter::eq(user_id)),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "ProjectFilter",
+        rename_all = "None"
+    )]
+    pub struct ProjectFilter {
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub id: Option<StringHashFilter>,
+    }
+
+    impl ProjectFilter {
+        pub fn id(id: impl ToString) -> Self {
+            Self {
+                id: Some(StringHashFilter::eq(id)),
+            }
+        }
+
+        pub fn any_of(ids: impl IntoIterator<Item = String>) -> Option<Self> {
+            Some(Self {
+                id: Some(StringHashFilter::any_of(ids)?),
+            })
+        }
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "TimeEntryFilter",
+        rename_all = "None"
+    )]
+    pub struct TimeEntryFilter {
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub id: Option<StringHashFilter>,
+    }
+
+    impl TimeEntryFilter {
+        pub fn id(id: impl ToString) -> Self {
+            Self {
+                id: Some(StringHashFilter::eq(id)),
+            }
+        }
+
+        pub fn any_of(ids: impl IntoIterator<Item = String>) -> Option<Self> {
+            Some(Self {
+                id: Some(StringHashFilter::any_of(ids)?),
+            })
+        }
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "TimeBlockFilter",
+        rename_all = "None"
+    )]
+    pub struct TimeBlockFilter {
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub id: Option<StringHashFilter>,
+    }
+
+    impl TimeBlockFilter {
+        pub fn id(id: impl ToString) -> Self {
+            Self {
+                id: Some(StringHashFilter::eq(id)),
+            }
+        }
+
+        pub fn any_of(ids: impl IntoIterator<Item = String>) -> Option<Self> {
+            Some(Self {
+                id: Some(StringHashFilter::any_of(ids)?),
+            })
+        }
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "InvoiceFilter",
+        rename_all = "None"
+    )]
+    pub struct InvoiceFilter {
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub id: Option<StringHashFilter>,
+    }
+
+    impl InvoiceFilter {
+        pub fn id(id: impl ToString) -> Self {
+            Self {
+                id: Some(StringHashFilter::eq(id)),
+            }
+        }
+
+        pub fn any_of(ids: impl IntoIterator<Item = String>) -> Option<Self> {
+            Some(Self {
+                id: Some(StringHashFilter::any_of(ids)?),
+            })
+        }
+    }
+
+    // ------ References ------
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "ClientRef",
+        rename_all = "None"
+    )]
+    pub struct ClientRef {
+        pub id: Option<String>,
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "ProjectRef",
+        rename_all = "None"
+    )]
+    pub struct ProjectRef {
+        pub id: Option<String>,
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "TimeEntryRef",
+        rename_all = "None"
+    )]
+    pub struct TimeEntryRef {
+        pub id: Option<String>,
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "TimeBlockRef",
+        rename_all = "None"
+    )]
+    pub struct TimeBlockRef {
+        pub id: Option<String>,
+    }
+
+    // ------ Time block ------
+
+    #[derive(cynic::Enum, Debug, Copy, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "TimeBlockStatus",
+        rename_all = "SCREAMING_SNAKE_CASE"
+    )]
+    pub enum TimeBlockStatus {
+        NonBillable,
+        Unpaid,
+        Paid,
+    }
+
+    // ------ Time entry ------
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "AddTimeEntryInput",
+        rename_all = "None"
+    )]
+    pub struct AddTimeEntryInput {
+        pub id: String,
+        pub name: String,
+        pub started: DateTime,
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub stopped: Option<DateTime>,
+        pub project: ProjectRef,
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "UpdateTimeEntryInput",
+        rename_all = "None"
+    )]
+    pub struct UpdateTimeEntryInput {
+        pub filter: TimeEntryFilter,
+        pub set: Option<TimeEntryPatch>,
+    }
+
+    impl UpdateTimeEntryInput {
+        pub fn new(filter: TimeEntryFilter, set: TimeEntryPatch) -> Self {
+            Self {
+                filter,
+                set: Some(set),
+            }
+        }
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone, Default)]
+    #[cynic(
+        schema_path = "schema.graphql",
+        query_module = "query_dsl",
+        graphql_type = "TimeEntryPatch",
+        rename_all = "None"
+    )]
+    pub struct TimeEntryPatch {
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub started: Option<DateTime>,
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub stopped: Option<DateTime>,
+        #[cynic(skip_serializing_if = "Option::is_none")]
+        pub project: Option<ProjectRef>,
+    }
 }
 
 mod query_dsl {
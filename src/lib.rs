@@ -5,14 +5,35 @@
 use seed::{prelude::*, *};
 use serde::Deserialize;
 
+use std::rc::Rc;
+
+mod analysis;
+mod auth;
+mod changes;
+mod duration;
+mod error_center;
+mod focus_session;
 mod page;
 mod graphql;
+mod notification;
+mod preferences;
+mod reminders;
+mod remote_data;
+mod store;
+mod timer;
+
+use focus_session::{FocusPhase, FocusSession};
+use preferences::Preferences;
+use reminders::Reminders;
+use store::Store;
 
 const CLIENTS_AND_PROJECTS: &str = "clients_and_projects";
 const TIME_TRACKER: &str = "time_tracker";
 const TIME_BLOCKS: &str = "time_blocks";
 const SETTINGS: &str = "settings";
 
+const REQUESTED_URL_STORAGE_KEY: &str = "requested_url";
+
 // ------ ------
 //     Init
 // ------ ------
@@ -20,7 +41,9 @@ const SETTINGS: &str = "settings";
 fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
     orders
         .subscribe(Msg::UrlChanged)
+        .subscribe(|_: store::Invalidate| Msg::InvalidateStore)
         .stream(streams::window_event(Ev::Click, |_| Msg::HideMenu))
+        .stream(streams::interval(1000, || Msg::OnSecondTick))
         .perform_cmd(async {
             Msg::AuthConfigFetched(
                 async {
@@ -32,16 +55,37 @@ fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
                 }
                 .await,
             )
+        })
+        .perform_cmd(async {
+            Msg::GraphQLConfigFetched(
+                async {
+                    fetch("/graphql_config.json")
+                        .await?
+                        .check_status()?
+                        .json()
+                        .await
+                }
+                .await,
+            )
         });
+    let ctx = Context {
+        auth_initialized: false,
+        user: None,
+        token: None,
+        graphql_config: None,
+        graphql_config_failed: false,
+        preferences: Preferences::load(),
+        store: Store::default(),
+        focus_session: None,
+        reminders: Reminders::default(),
+    };
     Model {
-        ctx: Context {
-            user: None,
-            token: None,
-        },
         base_url: url.to_base_url(),
-        page: Page::init(url, orders),
+        page: Page::init(url, orders, &ctx),
+        ctx,
         menu_visible: false,
         auth_config: None,
+        errors: Vec::new(),
     }
 }
 
@@ -49,6 +93,11 @@ fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
 struct AuthConfig {
     domain: String,
     client_id: String,
+    /// API the access token is issued for, Dgraph verifies it.
+    audience: String,
+    /// Backend that saves the profile changes through the Auth0 Management API.
+    #[serde(default)]
+    profile_endpoint: Option<String>,
 }
 
 // ------ ------
@@ -84,11 +133,59 @@ struct Model {
     page: Page,
     menu_visible: bool,
     auth_config: Option<AuthConfig>,
+    /// App-wide errors, e.g. records skipped while loading the store.
+    errors: Vec<Error>,
+}
+
+#[derive(Debug)]
+enum Error {
+    GraphQLConfig(fetch::FetchError),
+    /// The access token couldn't be fetched, e.g. the Auth0 session expired.
+    Token(String),
+    Store(graphql::GraphQLError),
+}
+
+impl error_center::Notice for Error {
+    fn summary(&self) -> String {
+        match self {
+            Self::GraphQLConfig(_) => "The server configuration couldn't be loaded.".to_owned(),
+            Self::Token(_) => "Your session couldn't be restored, please log in again.".to_owned(),
+            Self::Store(graphql_error) => graphql_error.to_string(),
+        }
+    }
+
+    fn details(&self) -> Option<String> {
+        match self {
+            Self::GraphQLConfig(fetch_error) => Some(format!("{:#?}", fetch_error)),
+            Self::Token(error) => Some(error.clone()),
+            Self::Store(graphql_error) => Some(graphql_error.details()),
+        }
+    }
 }
 
 struct Context {
+    auth_initialized: bool,
     user: Option<User>,
     token: Option<String>,
+    graphql_config: Option<graphql::Config>,
+    /// `graphql_config.json` is missing or invalid, protected pages can't be loaded.
+    graphql_config_failed: bool,
+    preferences: Preferences,
+    store: Store,
+    focus_session: Option<FocusSession>,
+    reminders: Reminders,
+}
+
+impl Context {
+    /// `graphql_config` with the access token header and the user's id.
+    fn authorized_graphql_config(&self) -> Option<graphql::Config> {
+        let user_id = self.user.as_ref().map(|user| user.sub.as_str());
+        Some(
+            self.graphql_config
+                .clone()?
+                .with_auth(self.token.as_deref(), user_id),
+        )
+    }
 }
 
 #[derive(Deserialize)]
@@ -104,6 +201,10 @@ struct User {
 
 enum Page {
     Home,
+    // Protected pages wait for the auth initialization, the token and `graphql_config.json`.
+    Loading,
+    LoadingFailed,
+    LogInRequired,
     ClientsAndProjects(page::clients_and_projects::Model),
     TimeTracker(page::time_tracker::Model),
     TimeBlocks(page::time_blocks::Model),
@@ -112,30 +213,120 @@ enum Page {
 }
 
 impl Page {
-    fn init(mut url: Url, orders: &mut impl Orders<Msg>) -> Self {
-        match url.remaining_path_parts().as_slice() {
-            [] => Self::Home,
-            [CLIENTS_AND_PROJECTS] => Self::ClientsAndProjects(page::clients_and_projects::init(
-                url,
-                &mut orders.proxy(Msg::ClientsAndProjectsMsg),
-            )),
-            [TIME_TRACKER] => Self::TimeTracker(page::time_tracker::init(
+    fn init(mut url: Url, orders: &mut impl Orders<Msg>, ctx: &Context) -> Self {
+        match (
+            url.remaining_path_parts().as_slice(),
+            ctx.authorized_graphql_config(),
+        ) {
+            ([], _) => Self::Home,
+            ([page], _) if is_protected(page) && ctx.auth_initialized && ctx.user.is_none() => {
+                Self::LogInRequired
+            }
+            ([page], _) if is_protected(page) && ctx.graphql_config_failed => Self::LoadingFailed,
+            ([page], _) if is_protected(page) && ctx.token.is_none() => Self::Loading,
+            ([CLIENTS_AND_PROJECTS], Some(_)) => {
+                Self::ClientsAndProjects(page::clients_and_projects::init(
+                    url,
+                    &mut orders.proxy(Msg::ClientsAndProjectsMsg),
+                    &ctx.store,
+                ))
+            }
+            ([TIME_TRACKER], Some(_)) => Self::TimeTracker(page::time_tracker::init(
                 url,
                 &mut orders.proxy(Msg::TimeTrackerMsg),
+                &ctx.store,
+                &ctx.preferences,
             )),
-            [TIME_BLOCKS] => Self::TimeBlocks(page::time_blocks::init(
+            ([TIME_BLOCKS], Some(_)) => Self::TimeBlocks(page::time_blocks::init(
                 url,
                 &mut orders.proxy(Msg::TimeBlocksMsg),
+                &ctx.store,
             )),
-            [SETTINGS] => Self::Settings(page::settings::init(
-                url,
-                &mut orders.proxy(Msg::SettingsMsg),
-            )),
+            ([SETTINGS], Some(_)) => {
+                let (username, email) = ctx
+                    .user
+                    .as_ref()
+                    .map(|user| (user.nickname.clone(), user.email.clone()))
+                    .unwrap_or_default();
+                Self::Settings(page::settings::init(
+                    url,
+                    &mut orders.proxy(Msg::SettingsMsg),
+                    username,
+                    email,
+                ))
+            }
+            ([CLIENTS_AND_PROJECTS | TIME_TRACKER | TIME_BLOCKS | SETTINGS], None) => Self::Loading,
             _ => Self::NotFound,
         }
     }
 }
 
+/// Starts or stops the timer of the project, e.g. for the focus session or a reminder.
+///
+/// The open time tracker page does it itself to keep its unsaved changes.
+fn start_or_stop_timer(
+    model: &mut Model,
+    client_id: ulid::Ulid,
+    project_id: ulid::Ulid,
+    work: bool,
+    orders: &mut impl Orders<Msg>,
+) {
+    if let Page::TimeTracker(_) = model.page {
+        orders.send_msg(Msg::TimeTrackerMsg(if work {
+            page::time_tracker::Msg::Start(client_id, project_id)
+        } else {
+            page::time_tracker::Msg::Stop(client_id, project_id)
+        }));
+        return;
+    }
+    let graphql_config = match model.ctx.authorized_graphql_config() {
+        Some(graphql_config) => graphql_config,
+        None => return,
+    };
+    let store = &mut model.ctx.store;
+    let saves = if work {
+        let single_active_timer = model.ctx.preferences.single_active_timer;
+        vec![timer::start(
+            store,
+            &graphql_config,
+            project_id,
+            String::new(),
+            single_active_timer,
+        )]
+    } else {
+        timer::stop(store, &graphql_config, project_id)
+    };
+    for timer::Save { change, request } in saves {
+        orders.perform_cmd(async move {
+            let error = match request {
+                Ok(request) => request.send().await.err(),
+                Err(error) => Some(error),
+            };
+            Msg::TimerSaved(change, error)
+        });
+    }
+    orders.notify(store::Changed);
+}
+
+/// Fetches the store once the token and `graphql_config.json` are available.
+fn fetch_store(ctx: &mut Context, orders: &mut impl Orders<Msg>) {
+    let graphql_config = match ctx.authorized_graphql_config() {
+        Some(graphql_config) if ctx.token.is_some() => graphql_config,
+        _ => return,
+    };
+    ctx.store.status = match ctx.store.status {
+        store::Status::NotLoaded | store::Status::Failed(_) => store::Status::Loading,
+        store::Status::Loaded => store::Status::Reloading,
+        // The running fetch will bring the latest data.
+        store::Status::Loading | store::Status::Reloading => return,
+    };
+    orders.perform_cmd(async move { Msg::StoreFetched(store::fetch(&graphql_config).await) });
+}
+
+fn is_protected(page: &str) -> bool {
+    [CLIENTS_AND_PROJECTS, TIME_TRACKER, TIME_BLOCKS, SETTINGS].contains(&page)
+}
+
 // ------ ------
 //    Update
 // ------ ------
@@ -149,17 +340,28 @@ enum Msg {
     TimeBlocksMsg(page::time_blocks::Msg),
     SettingsMsg(page::settings::Msg),
     AuthConfigFetched(fetch::Result<AuthConfig>),
+    GraphQLConfigFetched(fetch::Result<graphql::Config>),
     AuthInitialized(Result<JsValue, JsValue>),
+    TokenFetched(Result<JsValue, JsValue>),
     SignUp,
     LogIn,
     LogOut,
     RedirectingToSignUp(Result<(), JsValue>),
     RedirectingToLogIn(Result<(), JsValue>),
+    StoreFetched(graphql::Result<(Store, Vec<graphql::GraphQLError>)>),
+    InvalidateStore,
+    ClearErrors,
+    DismissError(usize),
+    OnSecondTick,
+    StartTimer(ulid::Ulid, ulid::Ulid),
+    StopTimer(ulid::Ulid, ulid::Ulid),
+    DismissReminder,
+    TimerSaved(store::Change, Option<graphql::GraphQLError>),
 }
 
 fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
     match msg {
-        Msg::UrlChanged(subs::UrlChanged(url)) => model.page = Page::init(url, orders),
+        Msg::UrlChanged(subs::UrlChanged(url)) => model.page = Page::init(url, orders, &model.ctx),
         Msg::ToggleMenu => model.menu_visible = not(model.menu_visible),
         Msg::HideMenu => {
             if model.menu_visible {
@@ -169,57 +371,146 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             }
         }
         Msg::ClientsAndProjectsMsg(msg) => {
-            if let Page::ClientsAndProjects(model) = &mut model.page {
+            if let (Page::ClientsAndProjects(page_model), Some(graphql_config)) =
+                (&mut model.page, model.ctx.authorized_graphql_config())
+            {
                 page::clients_and_projects::update(
                     msg,
-                    model,
+                    page_model,
+                    &graphql_config,
+                    &mut model.ctx.store,
                     &mut orders.proxy(Msg::ClientsAndProjectsMsg),
                 )
             }
         }
         Msg::TimeTrackerMsg(msg) => {
-            if let Page::TimeTracker(model) = &mut model.page {
-                page::time_tracker::update(msg, model, &mut orders.proxy(Msg::TimeTrackerMsg))
+            if let (Page::TimeTracker(page_model), Some(graphql_config)) =
+                (&mut model.page, model.ctx.authorized_graphql_config())
+            {
+                page::time_tracker::update(
+                    msg,
+                    page_model,
+                    &graphql_config,
+                    &model.ctx.preferences,
+                    &mut model.ctx.focus_session,
+                    &mut model.ctx.store,
+                    &mut orders.proxy(Msg::TimeTrackerMsg),
+                )
             }
         }
         Msg::TimeBlocksMsg(msg) => {
-            if let Page::TimeBlocks(model) = &mut model.page {
-                page::time_blocks::update(msg, model, &mut orders.proxy(Msg::TimeBlocksMsg))
+            if let (Page::TimeBlocks(page_model), Some(graphql_config)) =
+                (&mut model.page, model.ctx.authorized_graphql_config())
+            {
+                page::time_blocks::update(
+                    msg,
+                    page_model,
+                    &graphql_config,
+                    &mut model.ctx.store,
+                    &mut orders.proxy(Msg::TimeBlocksMsg),
+                )
             }
         }
         Msg::SettingsMsg(msg) => {
-            if let Page::Settings(model) = &mut model.page {
-                page::settings::update(msg, model, &mut orders.proxy(Msg::SettingsMsg))
+            if let (Page::Settings(page_model), Some(graphql_config)) =
+                (&mut model.page, model.ctx.authorized_graphql_config())
+            {
+                page::settings::update(
+                    msg,
+                    page_model,
+                    &graphql_config,
+                    &auth::Auth0,
+                    &mut model.ctx.preferences,
+                    &mut orders.proxy(Msg::SettingsMsg),
+                )
             }
         }
         Msg::AuthConfigFetched(Ok(auth_config)) => {
             let domain = auth_config.domain.clone();
             let client_id = auth_config.client_id.clone();
+            let audience = auth_config.audience.clone();
+            let profile_endpoint = auth_config.profile_endpoint.clone();
 
-            orders.perform_cmd(async { Msg::AuthInitialized(init_auth(domain, client_id).await) });
+            orders.perform_cmd(async {
+                Msg::AuthInitialized(init_auth(domain, client_id, audience, profile_endpoint).await)
+            });
             model.auth_config = Some(auth_config);
         },
         Msg::AuthConfigFetched(Err(fetch_error)) => error!("AuthConfig fetch failed!", fetch_error),
+        Msg::GraphQLConfigFetched(Ok(graphql_config)) => {
+            model.ctx.graphql_config = Some(graphql_config);
+            if matches!(model.ctx.store.status, store::Status::NotLoaded) {
+                fetch_store(&mut model.ctx, orders);
+            }
+            if let Page::Loading = model.page {
+                model.page = Page::init(Url::current(), orders, &model.ctx);
+            }
+        }
+        Msg::GraphQLConfigFetched(Err(fetch_error)) => {
+            error!("GraphQLConfig fetch failed!", fetch_error);
+            model.errors.push(Error::GraphQLConfig(fetch_error));
+            model.ctx.graphql_config_failed = true;
+            if let Page::Loading = model.page {
+                model.page = Page::init(Url::current(), orders, &model.ctx);
+            }
+        }
         Msg::AuthInitialized(Ok(user)) => {
+            model.ctx.auth_initialized = true;
             if not(user.is_undefined()) {
                 match serde_wasm_bindgen::from_value(user) {
-                    Ok(user) => model.ctx.user = Some(user),
+                    Ok(user) => {
+                        model.ctx.user = Some(user);
+                        orders.perform_cmd(async { Msg::TokenFetched(get_token().await) });
+                    }
                     Err(error) => error!("User deserialization failed!", error),
                 }
             }
 
             let search = model.base_url.search_mut();
             if search.remove("code").is_some() && search.remove("state").is_some() {
-                model.base_url.go_and_replace();
+                // Return to the page that was requested before the redirect to Auth0.
+                let requested_url = SessionStorage::get::<String>(REQUESTED_URL_STORAGE_KEY)
+                    .ok()
+                    .and_then(|url| url.parse::<Url>().ok());
+                let _ = SessionStorage::remove(REQUESTED_URL_STORAGE_KEY);
+
+                if let Some(requested_url) = requested_url {
+                    requested_url.go_and_replace();
+                } else {
+                    model.base_url.go_and_replace();
+                }
             }
+            model.page = Page::init(Url::current(), orders, &model.ctx);
         }
         Msg::AuthInitialized(Err(error)) => {
             error!("Auth initialization failed!", error);
+            model.ctx.auth_initialized = true;
+            model.page = Page::init(Url::current(), orders, &model.ctx);
+        }
+        Msg::TokenFetched(Ok(token)) => {
+            model.ctx.token = token.as_string();
+            if matches!(model.ctx.store.status, store::Status::NotLoaded) {
+                fetch_store(&mut model.ctx, orders);
+            }
+            // Reload the page data with the user's credentials.
+            model.page = Page::init(Url::current(), orders, &model.ctx);
+        }
+        Msg::TokenFetched(Err(error)) => {
+            error!("Token fetch failed!", error);
+            model.errors.push(Error::Token(format!("{:?}", error)));
+            // Without a token the user has to log in again.
+            model.ctx.user = None;
+            model.page = Page::init(Url::current(), orders, &model.ctx);
         }
         Msg::SignUp => {
             orders.perform_cmd(async { Msg::RedirectingToSignUp(redirect_to_sign_up().await) });
         },
         Msg::LogIn => {
+            if let Err(error) =
+                SessionStorage::insert(REQUESTED_URL_STORAGE_KEY, &Url::current().to_string())
+            {
+                error!("Cannot remember the requested page!", error);
+            }
             orders.perform_cmd(async { Msg::RedirectingToLogIn(redirect_to_log_in().await) });
         },
         Msg::RedirectingToSignUp(result) => {
@@ -237,15 +528,112 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 error!("Cannot log out!", error);
             } else {
                 model.ctx.user = None;
+                model.ctx.token = None;
+                model.ctx.store = Store::default();
             }
         },
+        Msg::StoreFetched(Ok((fetched_store, invalid_data))) => {
+            if not(invalid_data.is_empty()) {
+                error!("Invalid records skipped!", invalid_data);
+            }
+            model.ctx.store = fetched_store;
+            // Errors of the previous fetch are replaced.
+            model.errors.retain(|error| {
+                not(matches!(error, Error::Store(graphql::GraphQLError::InvalidData { .. })))
+            });
+            model.errors.extend(invalid_data.into_iter().map(Error::Store));
+            orders.notify(store::Changed);
+        }
+        Msg::StoreFetched(Err(graphql_error)) => {
+            error!("Store fetch failed!", graphql_error);
+            model.ctx.store.status = match model.ctx.store.status {
+                store::Status::Reloading => {
+                    // The old entities are still shown, only report the error.
+                    model.errors.push(Error::Store(graphql_error));
+                    store::Status::Loaded
+                }
+                _ => store::Status::Failed(Rc::new(graphql_error)),
+            };
+            orders.notify(store::Changed);
+        }
+        Msg::InvalidateStore => fetch_store(&mut model.ctx, orders),
+        Msg::ClearErrors => model.errors.clear(),
+        Msg::DismissError(index) => {
+            error_center::remove_group(&mut model.errors, index);
+        }
+        Msg::OnSecondTick => {
+            // The time tracker ticks with the app, so there is only one clock.
+            if let Page::TimeTracker(_) = model.page {
+                orders.send_msg(Msg::TimeTrackerMsg(page::time_tracker::Msg::OnSecondTick));
+            }
+            let now = chrono::Local::now();
+            let ctx = &mut model.ctx;
+            let reminder_changed = ctx.reminders.check(&ctx.store, now, &ctx.preferences, orders);
+
+            let preferences = &ctx.preferences;
+            let focus_session = match ctx.focus_session.as_mut() {
+                Some(focus_session) => focus_session,
+                // The time tracker re-renders its running timers itself.
+                None => {
+                    if not(reminder_changed) {
+                        orders.skip();
+                    }
+                    return;
+                }
+            };
+            if now >= focus_session.phase_ends {
+                let finished_phase = focus_session.phase;
+                focus_session.advance(now, &preferences.pomodoro);
+                orders.perform_cmd(notification::show(
+                    format!("{} is over", finished_phase.label()),
+                    format!(
+                        "{} until {}.",
+                        focus_session.phase.label(),
+                        preferences.format_time(&focus_session.phase_ends)
+                    ),
+                ));
+                let (client_id, project_id) = (focus_session.client_id, focus_session.project_id);
+                // Only work intervals are tracked.
+                let work = focus_session.phase == FocusPhase::Work;
+                start_or_stop_timer(model, client_id, project_id, work, orders);
+            }
+            if let Some(focus_session) = &model.ctx.focus_session {
+                document().set_title(&format!(
+                    "{} {} | {}",
+                    model.ctx.preferences.format_duration(&(focus_session.phase_ends - now)),
+                    focus_session.phase.label(),
+                    page::time_tracker::TITLE
+                ));
+            }
+        }
+        Msg::StartTimer(client_id, project_id) => {
+            start_or_stop_timer(model, client_id, project_id, true, orders);
+        }
+        Msg::StopTimer(client_id, project_id) => {
+            start_or_stop_timer(model, client_id, project_id, false, orders);
+        }
+        Msg::DismissReminder => model.ctx.reminders.reminder = None,
+        Msg::TimerSaved(_, None) => {}
+        Msg::TimerSaved(change, Some(graphql_error)) => {
+            model.ctx.store.revert(change);
+            orders.notify(store::Changed);
+            model.errors.push(Error::Store(graphql_error));
+        }
     }
 }
 
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(catch)]
-    async fn init_auth(domain: String, client_id: String) -> Result<JsValue, JsValue>;
+    async fn init_auth(
+        domain: String,
+        client_id: String,
+        audience: String,
+        profile_endpoint: Option<String>,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch)]
+    async fn get_token() -> Result<JsValue, JsValue>;
 
     #[wasm_bindgen(catch)]
     async fn redirect_to_sign_up() -> Result<(), JsValue>;
@@ -269,21 +657,70 @@ fn view(model: &Model) -> Vec<Node<Msg>> {
             model.ctx.user.as_ref(),
             &model.page,
         ),
-        view_content(&model.page, &model.base_url),
+        view_content(&model.page, &model.base_url, &model.ctx),
+        view_errors(&model.errors, &model.page),
+    ]
+}
+
+/// App-wide errors and the errors of the current page in the bottom right corner.
+fn view_errors(errors: &[Error], page: &Page) -> Node<Msg> {
+    div![
+        style! {
+            St::Position => "fixed",
+            St::Right => rem(1),
+            St::Bottom => rem(1),
+            St::ZIndex => 40,
+            St::MaxWidth => rem(28),
+        },
+        error_center::view(errors, Msg::DismissError, None, || Msg::ClearErrors),
+        match page {
+            Page::ClientsAndProjects(model) => {
+                page::clients_and_projects::view_errors(model).map_msg(Msg::ClientsAndProjectsMsg)
+            }
+            Page::TimeTracker(model) => {
+                page::time_tracker::view_errors(model).map_msg(Msg::TimeTrackerMsg)
+            }
+            Page::TimeBlocks(model) => {
+                page::time_blocks::view_errors(model).map_msg(Msg::TimeBlocksMsg)
+            }
+            Page::Settings(model) => page::settings::view_errors(model).map_msg(Msg::SettingsMsg),
+            Page::Home
+            | Page::Loading
+            | Page::LoadingFailed
+            | Page::LogInRequired
+            | Page::NotFound => empty![],
+        },
     ]
 }
 
-fn view_content(page: &Page, base_url: &Url) -> Node<Msg> {
+fn view_content(page: &Page, base_url: &Url, ctx: &Context) -> Node<Msg> {
+    let preferences = &ctx.preferences;
     div![
         C!["container"],
+        ctx.reminders.reminder.as_ref().map(|reminder| {
+            reminders::view(
+                reminder,
+                &ctx.store,
+                preferences,
+                Msg::StartTimer,
+                Msg::StopTimer,
+                || Msg::DismissReminder,
+            )
+        }),
         match page {
             Page::Home => page::home::view(base_url),
-            Page::ClientsAndProjects(model) =>
-                page::clients_and_projects::view(model).map_msg(Msg::ClientsAndProjectsMsg),
+            Page::Loading => progress![C!["progress", "is-link", "mt-6"]],
+            Page::LoadingFailed => page::loading_failed::view(),
+            Page::LogInRequired => page::log_in_required::view(|| Msg::LogIn),
+            Page::ClientsAndProjects(model) => page::clients_and_projects::view(model, preferences)
+                .map_msg(Msg::ClientsAndProjectsMsg),
             Page::TimeTracker(model) =>
-                page::time_tracker::view(model).map_msg(Msg::TimeTrackerMsg),
-            Page::TimeBlocks(model) => page::time_blocks::view(model).map_msg(Msg::TimeBlocksMsg),
-            Page::Settings(model) => page::settings::view(model).map_msg(Msg::SettingsMsg),
+                page::time_tracker::view(model, ctx.focus_session.as_ref(), preferences)
+                    .map_msg(Msg::TimeTrackerMsg),
+            Page::TimeBlocks(model) =>
+                page::time_blocks::view(model, preferences).map_msg(Msg::TimeBlocksMsg),
+            Page::Settings(model) =>
+                page::settings::view(model, preferences).map_msg(Msg::SettingsMsg),
             Page::NotFound => page::not_found::view(),
         }
     ]
@@ -0,0 +1,26 @@
+use seed::{prelude::*, *};
+
+/// Asks for the permission to show notifications.
+///
+/// Browsers only allow it in reaction to a user action like a click.
+pub async fn request_permission() {
+    if let Err(error) = request_notification_permission().await {
+        error!("Notification permission cannot be requested!", error);
+    }
+}
+
+/// Shows a browser notification. Notifications are best-effort, failures are only logged.
+pub async fn show(title: String, body: String) {
+    if let Err(error) = show_notification(title, body).await {
+        error!("Notification cannot be shown!", error);
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    async fn request_notification_permission() -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch)]
+    async fn show_notification(title: String, body: String) -> Result<(), JsValue>;
+}
@@ -1,5 +1,7 @@
 pub mod clients_and_projects;
 pub mod home;
+pub mod loading_failed;
+pub mod log_in_required;
 pub mod not_found;
 pub mod settings;
 pub mod time_blocks;
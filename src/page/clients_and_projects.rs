@@ -1,14 +1,16 @@
 use seed::{prelude::*, *};
 
-use chrono::prelude::*;
 use ulid::Ulid;
 
-use cynic::QueryBuilder;
+use cynic::{MutationBuilder, QueryBuilder};
 
 use std::collections::BTreeMap;
-use std::convert::identity;
 
+use crate::changes::{self, Changes};
 use crate::graphql;
+use crate::preferences::Preferences;
+use crate::remote_data::{self, RemoteData};
+use crate::store::{self, Snapshot, Store};
 
 const PRIMARY_COLOR: &str = "#00d1b2";
 
@@ -19,47 +21,89 @@ type ProjectId = Ulid;
 //     Init
 // ------ ------
 
-pub fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
-    orders.perform_cmd(async { Msg::ClientsFetched(request_clients().await) });
+pub fn init(url: Url, orders: &mut impl Orders<Msg>, store: &Store) -> Model {
     Model {
-        changes_status: ChangesStatus::NoChanges,
-        errors: Vec::new(),
+        changes: Changes::default(),
 
-        clients: RemoteData::Loading,
+        clients: clients_from_store(store),
+        store_changed_handle: orders
+            .subscribe_with_handle(|_: store::Changed| Msg::StoreChanged),
     }
 }
 
-async fn request_clients() -> graphql::Result<BTreeMap<ClientId, Client>> {
-    use graphql::queries::clients_with_projects as query_mod;
+fn clients_from_store(store: &Store) -> RemoteData<BTreeMap<ClientId, Client>> {
+    store.remote_data().map(|store| {
+        let project_mapper = |(project_id, project): (ProjectId, &store::Project)| {
+            (
+                project_id,
+                Project {
+                    name: project.name.clone(),
+                    name_input: ElRef::new(),
+                },
+            )
+        };
+
+        let client_mapper = |(client_id, client): (&ClientId, &store::Client)| {
+            (
+                *client_id,
+                Client {
+                    name: client.name.clone(),
+                    projects: store.projects_of(*client_id).map(project_mapper).collect(),
+                    name_input: ElRef::new(),
+                },
+            )
+        };
 
-    let project_mapper = |project: query_mod::Project| {
-        (
-            project.id.parse().expect("parse project Ulid"),
-            Project {
-                name: project.name,
-                name_input: ElRef::new(),
-            },
-        )
-    };
-
-    let client_mapper = |client: query_mod::Client| {
-        (
-            client.id.parse().expect("parse client Ulid"),
-            Client {
-                name: client.name,
-                projects: client.projects.into_iter().map(project_mapper).collect(),
-                name_input: ElRef::new(),
-            },
-        )
-    };
+        store.clients.iter().map(client_mapper).collect()
+    })
+}
+
+async fn request_client_descendants(
+    graphql_config: &graphql::Config,
+    client_id: ClientId,
+) -> graphql::Result<ClientDescendants> {
+    use graphql::queries::client_with_descendant_ids as query_mod;
+
+    let operation = query_mod::Query::build(query_mod::Arguments {
+        id: client_id.to_string(),
+    });
+    let client = graphql::send_operation(graphql_config, operation)
+        .await?
+        .get_client;
+
+    let mut descendants = ClientDescendants::default();
+    if let Some(client) = client {
+        for time_block in client.time_blocks {
+            descendants.time_block_ids.push(time_block.id);
+            if let Some(invoice) = time_block.invoice {
+                descendants.invoice_ids.push(invoice.id);
+            }
+        }
+        for project in client.projects {
+            descendants.project_ids.push(project.id);
+            descendants
+                .time_entry_ids
+                .extend(project.time_entries.into_iter().map(|time_entry| time_entry.id));
+        }
+    }
+    Ok(descendants)
+}
+
+async fn request_project_time_entry_ids(
+    graphql_config: &graphql::Config,
+    project_id: ProjectId,
+) -> graphql::Result<Vec<String>> {
+    use graphql::queries::project_with_descendant_ids as query_mod;
 
-    Ok(graphql::send_operation(query_mod::Query::build(()))
+    let operation = query_mod::Query::build(query_mod::Arguments {
+        id: project_id.to_string(),
+    });
+    Ok(graphql::send_operation(graphql_config, operation)
         .await?
-        .query_client
-        .expect("get clients")
+        .get_project
         .into_iter()
-        .filter_map(identity)
-        .map(client_mapper)
+        .flat_map(|project| project.time_entries)
+        .map(|time_entry| time_entry.id)
         .collect())
 }
 
@@ -68,41 +112,10 @@ async fn request_clients() -> graphql::Result<BTreeMap<ClientId, Client>> {
 // ------ ------
 
 pub struct Model {
-    changes_status: ChangesStatus,
-    errors: Vec<graphql::GraphQLError>,
+    changes: Changes,
 
     clients: RemoteData<BTreeMap<ClientId, Client>>,
-}
-
-// ----- Remote Data ------
-
-enum RemoteData<T> {
-    NotAsked,
-    Loading,
-    Loaded(T),
-}
-
-impl<T> RemoteData<T> {
-    fn loaded(&self) -> Option<&T> {
-        if let Self::Loaded(data) = self {
-            Some(data)
-        } else {
-            None
-        }
-    }
-    fn loaded_mut(&mut self) -> Option<&mut T> {
-        if let Self::Loaded(data) = self {
-            Some(data)
-        } else {
-            None
-        }
-    }
-}
-
-enum ChangesStatus {
-    NoChanges,
-    Saving { requests_in_flight: usize },
-    Saved(DateTime<Local>),
+    store_changed_handle: SubHandle,
 }
 
 #[derive(Debug)]
@@ -118,18 +131,27 @@ struct Project {
     name_input: ElRef<web_sys::HtmlInputElement>,
 }
 
+#[derive(Debug, Default)]
+pub struct ClientDescendants {
+    project_ids: Vec<String>,
+    time_entry_ids: Vec<String>,
+    time_block_ids: Vec<String>,
+    invoice_ids: Vec<String>,
+}
+
 // ------ ------
 //    Update
 // ------ ------
 
 pub enum Msg {
-    ClientsFetched(graphql::Result<BTreeMap<ClientId, Client>>),
-    ChangesSaved(Option<FetchError>),
-    ClearErrors,
+    StoreChanged,
+    RetryStoreFetch,
+    Changes(changes::Msg),
 
     // ------ Client ------
     AddClient,
     DeleteClient(ClientId),
+    ClientDescendantsFetched(ClientId, graphql::Result<ClientDescendants>),
     FocusClientName(ClientId),
 
     ClientNameChanged(ClientId, String),
@@ -138,35 +160,41 @@ pub enum Msg {
     // ------ Project ------
     AddProject(ClientId),
     DeleteProject(ClientId, ProjectId),
+    ProjectTimeEntryIdsFetched(ClientId, ProjectId, graphql::Result<Vec<String>>),
     FocusProjectName(ClientId, ProjectId),
 
     ProjectNameChanged(ClientId, ProjectId, String),
     SaveProjectName(ClientId, ProjectId),
 }
 
-pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
+pub fn update(
+    msg: Msg,
+    model: &mut Model,
+    graphql_config: &graphql::Config,
+    store: &mut Store,
+    orders: &mut impl Orders<Msg>,
+) {
     match msg {
-        Msg::ClientsFetched(Ok(clients)) => {
-            log!("Msg::ClientsFetched", clients);
-            model.clients = RemoteData::Loaded(clients);
-        }
-        Msg::ClientsFetched(Err(graphql_error)) => {
-            model.errors.push(graphql_error);
-        }
-
-        Msg::ChangesSaved(None) => {
-            log!("Msg::ChangesSaved");
-        }
-        Msg::ChangesSaved(Some(fetch_error)) => {
-            log!("Msg::ChangesSaved", fetch_error);
+        Msg::StoreChanged => model.clients = clients_from_store(store),
+        Msg::RetryStoreFetch => {
+            model.clients = RemoteData::Loading;
+            orders.notify(store::Invalidate);
         }
 
-        Msg::ClearErrors => {
-            log!("Msg::ClearErrors");
+        Msg::Changes(msg) => {
+            let failed_change =
+                changes::update(msg, &mut model.changes, &mut orders.proxy(Msg::Changes));
+            if let Some(change) = failed_change {
+                // Undo the optimistic update, `StoreChanged` rebuilds the page.
+                store.revert(change);
+                orders.notify(store::Changed);
+            }
         }
 
         // ------ Client ------
         Msg::AddClient => {
+            use graphql::mutations::add_client as mutation_mod;
+
             if let Some(clients) = model.clients.loaded_mut() {
                 let client_id = ClientId::new();
                 let client = Client {
@@ -174,25 +202,83 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                     projects: BTreeMap::new(),
                     name_input: ElRef::new(),
                 };
-                // @TODO send request
+                let input = mutation_mod::AddClientInput {
+                    id: client_id.to_string(),
+                    name: client.name.clone(),
+                    projects: Vec::new(),
+                    time_blocks: Vec::new(),
+                    user: graphql_config.user_id(),
+                };
+                let before = Snapshot::default().client(store, client_id);
+                store.clients.insert(
+                    client_id,
+                    store::Client {
+                        name: client.name.clone(),
+                    },
+                );
                 clients.insert(client_id, client);
                 orders.after_next_render(move |_| Msg::FocusClientName(client_id));
+
+                let operation =
+                    mutation_mod::Mutation::build(mutation_mod::Arguments { input: vec![input] });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
             }
         }
         Msg::DeleteClient(client_id) => {
-            let mut delete_client = move |client_id| -> Option<()> {
-                let clients = model.clients.loaded_mut()?;
-                let client_name = clients.get(&client_id).map(|client| &client.name)?;
-
-                if let Ok(true) = window()
-                    .confirm_with_message(&format!("Client \"{}\" will be deleted.", client_name))
-                {
-                    clients.remove(&client_id);
-                    // @TODO send request
-                }
-                Some(())
+            let graphql_config = graphql_config.clone();
+            orders.perform_cmd(async move {
+                Msg::ClientDescendantsFetched(
+                    client_id,
+                    request_client_descendants(&graphql_config, client_id).await,
+                )
+            });
+        }
+        Msg::ClientDescendantsFetched(client_id, Ok(descendants)) => {
+            let clients = match model.clients.loaded_mut() {
+                Some(clients) => clients,
+                None => return,
+            };
+            let client_name = match clients.get(&client_id) {
+                Some(client) => &client.name,
+                None => return,
             };
-            delete_client(client_id);
+            let message = format!(
+                "Client \"{}\" will be deleted together with {} project(s), {} time entries, {} time block(s) and {} invoice(s).",
+                client_name,
+                descendants.project_ids.len(),
+                descendants.time_entry_ids.len(),
+                descendants.time_block_ids.len(),
+                descendants.invoice_ids.len(),
+            );
+
+            if let Ok(true) = window().confirm_with_message(&message) {
+                clients.remove(&client_id);
+                let change = store.remove_client(client_id);
+
+                let deletes = graphql::mutations::Deletes {
+                    invoice_ids: descendants.invoice_ids,
+                    time_block_ids: descendants.time_block_ids,
+                    time_entry_ids: descendants.time_entry_ids,
+                    project_ids: descendants.project_ids,
+                    client_ids: vec![client_id.to_string()],
+                };
+                if let Some(operation) = deletes.mutation() {
+                    model.changes.save(
+                        graphql_config,
+                        &mut orders.proxy(Msg::Changes),
+                        operation,
+                        change,
+                    );
+                }
+            }
+        }
+        Msg::ClientDescendantsFetched(_, Err(graphql_error)) => {
+            model.changes.errors.push(graphql_error.into());
         }
         Msg::FocusClientName(client_id) => {
             let mut focus_client_name = move |name| -> Option<()> {
@@ -214,11 +300,39 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             set_client_name(name);
         }
         Msg::SaveClientName(client_id) => {
+            use graphql::mutations::update_client as mutation_mod;
+
             log!("Msg::SaveClientName", client_id);
+            let client = model
+                .clients
+                .loaded()
+                .and_then(|clients| clients.get(&client_id));
+
+            if let Some(client) = client {
+                let before = Snapshot::default().client(store, client_id);
+                if let Some(stored_client) = store.clients.get_mut(&client_id) {
+                    stored_client.name = client.name.clone();
+                }
+                let input = mutation_mod::UpdateClientInput::new(
+                    client_id,
+                    mutation_mod::ClientPatch {
+                        name: Some(client.name.clone()),
+                    },
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
         }
 
         // ------ Project ------
         Msg::AddProject(client_id) => {
+            use graphql::mutations::add_project as mutation_mod;
+
             let mut add_project = move |client_id| -> Option<()> {
                 let projects = &mut model.clients.loaded_mut()?.get_mut(&client_id)?.projects;
 
@@ -227,29 +341,82 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                     name: "".to_owned(),
                     name_input: ElRef::new(),
                 };
-                // @TODO: Send request.
+                let input = mutation_mod::AddProjectInput {
+                    id: project_id.to_string(),
+                    name: project.name.clone(),
+                    time_entries: Vec::new(),
+                    client: graphql::types::ClientRef {
+                        id: Some(client_id.to_string()),
+                    },
+                };
+                let before = Snapshot::default().project(store, project_id);
+                store.projects.insert(
+                    project_id,
+                    store::Project {
+                        client_id,
+                        name: project.name.clone(),
+                    },
+                );
                 projects.insert(project_id, project);
                 orders.after_next_render(move |_| Msg::FocusProjectName(client_id, project_id));
 
+                let operation =
+                    mutation_mod::Mutation::build(mutation_mod::Arguments { input: vec![input] });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
                 Some(())
             };
             add_project(client_id);
         }
         Msg::DeleteProject(client_id, project_id) => {
+            let graphql_config = graphql_config.clone();
+            orders.perform_cmd(async move {
+                Msg::ProjectTimeEntryIdsFetched(
+                    client_id,
+                    project_id,
+                    request_project_time_entry_ids(&graphql_config, project_id).await,
+                )
+            });
+        }
+        Msg::ProjectTimeEntryIdsFetched(client_id, project_id, Ok(time_entry_ids)) => {
             let mut delete_project = move |client_id, project_id| -> Option<()> {
                 let projects = &mut model.clients.loaded_mut()?.get_mut(&client_id)?.projects;
                 let project_name = projects.get(&project_id).map(|project| &project.name)?;
+                let message = format!(
+                    "Project \"{}\" will be deleted together with {} time entries.",
+                    project_name,
+                    time_entry_ids.len(),
+                );
 
-                if let Ok(true) = window()
-                    .confirm_with_message(&format!("Project \"{}\" will be deleted.", project_name))
-                {
+                if let Ok(true) = window().confirm_with_message(&message) {
                     projects.remove(&project_id);
-                    // @TODO: Send request.
+                    let change = store.remove_project(project_id);
+
+                    let deletes = graphql::mutations::Deletes {
+                        time_entry_ids,
+                        project_ids: vec![project_id.to_string()],
+                        ..Default::default()
+                    };
+                    if let Some(operation) = deletes.mutation() {
+                        model.changes.save(
+                            graphql_config,
+                            &mut orders.proxy(Msg::Changes),
+                            operation,
+                            change,
+                        );
+                    }
                 }
                 Some(())
             };
             delete_project(client_id, project_id);
         }
+        Msg::ProjectTimeEntryIdsFetched(_, _, Err(graphql_error)) => {
+            model.changes.errors.push(graphql_error.into());
+        }
         Msg::FocusProjectName(client_id, project_id) => {
             let mut focus_project_name = move |client_id, project_id| -> Option<()> {
                 model
@@ -280,7 +447,34 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             set_project_name(name);
         }
         Msg::SaveProjectName(client_id, project_id) => {
+            use graphql::mutations::update_project as mutation_mod;
+
             log!("Msg::SaveProjectName", client_id, project_id);
+            let project = model
+                .clients
+                .loaded()
+                .and_then(|clients| clients.get(&client_id))
+                .and_then(|client| client.projects.get(&project_id));
+
+            if let Some(project) = project {
+                let before = Snapshot::default().project(store, project_id);
+                if let Some(stored_project) = store.projects.get_mut(&project_id) {
+                    stored_project.name = project.name.clone();
+                }
+                let input = mutation_mod::UpdateProjectInput::new(
+                    project_id,
+                    mutation_mod::ProjectPatch {
+                        name: Some(project.name.clone()),
+                    },
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
         }
     }
 }
@@ -289,31 +483,34 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
 //     View
 // ------ ------
 
-pub fn view(model: &Model) -> Node<Msg> {
+pub fn view(model: &Model, preferences: &Preferences) -> Node<Msg> {
     section![
-        h1![C!["title", "ml-6", "my-6"], "Clients & Projects",],
+        h1![
+            C!["title", "ml-6", "my-6"],
+            "Clients & Projects",
+            changes::view_status(&model.changes.status, preferences),
+        ],
         div![
             C!["columns", "is-centered"],
             div![
                 C!["column", "is-half"],
                 view_add_client_button(),
-                match &model.clients {
-                    RemoteData::NotAsked | RemoteData::Loading => {
-                        progress![C!["progress", "is-link", "mt-6"]].into_nodes()
-                    }
-                    RemoteData::Loaded(clients) => {
-                        clients
-                            .iter()
-                            .rev()
-                            .map(|(client_id, client)| view_client(*client_id, client))
-                            .collect()
-                    }
-                }
+                remote_data::view(&model.clients, || Msg::RetryStoreFetch, |clients| {
+                    clients
+                        .iter()
+                        .rev()
+                        .map(|(client_id, client)| view_client(*client_id, client))
+                        .collect()
+                })
             ]
         ]
     ]
 }
 
+pub fn view_errors(model: &Model) -> Node<Msg> {
+    changes::view_errors(&model.changes).map_msg(Msg::Changes)
+}
+
 fn view_add_client_button() -> Node<Msg> {
     div![
         C!["level", "is-mobile"],
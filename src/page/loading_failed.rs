@@ -0,0 +1,12 @@
+use seed::{prelude::*, *};
+
+pub fn view<Ms>() -> Node<Ms> {
+    section![
+        C!["hero", "is-medium", "ml-6"],
+        div![
+            C!["hero-body"],
+            h1![C!["title", "is-size-1"], "Loading failed",],
+            h2![C!["subtitle", "is-size-3"], "The app couldn't be loaded, please try again later.",]
+        ]
+    ]
+}
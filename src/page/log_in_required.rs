@@ -0,0 +1,17 @@
+use seed::{prelude::*, *};
+
+pub fn view<Ms: 'static>(on_log_in: impl Fn() -> Ms + Clone + 'static) -> Node<Ms> {
+    section![
+        C!["hero", "is-medium", "ml-6"],
+        div![
+            C!["hero-body"],
+            h1![C!["title", "is-size-1"], "Please log in",],
+            h2![C!["subtitle", "is-size-3"], "This page is only for logged-in users.",],
+            a![
+                C!["button", "is-primary", "mt-5", "is-size-5"],
+                ev(Ev::Click, move |_| on_log_in()),
+                strong!["Log in"],
+            ],
+        ]
+    ]
+}
@@ -1,24 +1,88 @@
 use seed::{prelude::*, *};
-use chrono::prelude::*;
+
+use cynic::QueryBuilder;
+
+use std::convert::identity;
+
+use crate::auth::{AuthError, AuthProvider, UserChanges};
+use crate::changes::{self, ChangesStatus};
+use crate::error_center::{self, Notice};
+use crate::graphql;
+use crate::preferences::{
+    ClockFormat, DateFormat, DurationFormat, Preferences, TimeZone, WeekStart, WorkingDay,
+    IDLE_THRESHOLD_MINUTES,
+};
+use crate::notification;
+use crate::store;
+
+const MIN_PASSWORD_LENGTH: usize = 8;
+const DELETE_ACCOUNT_CONFIRMATION: &str = "delete my account";
 
 // ------ ------
 //     Init
 // ------ ------
 
-pub fn init(url: Url, _: &mut impl Orders<Msg>) -> Model {
+pub fn init(url: Url, _: &mut impl Orders<Msg>, username: String, email: String) -> Model {
     Model {
         changes_status: ChangesStatus::NoChanges,
         errors: Vec::new(),
 
+        saved_username: username.clone(),
+        saved_email: email.clone(),
+
         form: Form {
-            username: String::new(),
-            email: String::new(),
+            username,
+            email,
             password: String::new(),
             confirm_password: String::new(),
 
             errors: FormErrors::default(),
+        },
+        working_day_error: None,
+        delete_account_confirmation: String::new(),
+    }
+}
+
+async fn request_account_data(graphql_config: &graphql::Config) -> graphql::Result<AccountData> {
+    use graphql::queries::clients_with_descendant_ids as query_mod;
+
+    let operation = query_mod::Query::build(query_mod::Arguments {
+        filter: graphql::types::ClientFilter::user(graphql_config.user_id()),
+    });
+    let clients = graphql::send_operation(graphql_config, operation)
+        .await?
+        .query_client
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(identity);
+
+    let mut account_data = AccountData::default();
+    for client in clients {
+        account_data.client_ids.push(client.id);
+        for time_block in client.time_blocks {
+            account_data.time_block_ids.push(time_block.id);
+            if let Some(invoice) = time_block.invoice {
+                account_data.invoice_ids.push(invoice.id);
+            }
+        }
+        for project in client.projects {
+            account_data.project_ids.push(project.id);
+            account_data
+                .time_entry_ids
+                .extend(project.time_entries.into_iter().map(|time_entry| time_entry.id));
         }
     }
+    Ok(account_data)
+}
+
+async fn delete_account(
+    graphql_config: &graphql::Config,
+    deletes: graphql::mutations::Deletes,
+) -> graphql::Result<()> {
+    match deletes.mutation() {
+        Some(operation) => graphql::send_operation(graphql_config, operation).await,
+        None => Ok(()),
+    }
 }
 
 // ------ ------
@@ -27,15 +91,49 @@ pub fn init(url: Url, _: &mut impl Orders<Msg>) -> Model {
 
 pub struct Model {
     changes_status: ChangesStatus,
-    errors: Vec<FetchError>,
+    errors: Vec<Error>,
+
+    saved_username: String,
+    saved_email: String,
 
     form: Form,
+    // The day (Monday is 0) with rejected working hours and why.
+    working_day_error: Option<(usize, String)>,
+    delete_account_confirmation: String,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Auth(AuthError),
+    GraphQL(graphql::GraphQLError),
+}
+
+impl From<AuthError> for Error {
+    fn from(auth_error: AuthError) -> Self {
+        Self::Auth(auth_error)
+    }
 }
 
-enum ChangesStatus {
-    NoChanges,
-    Saving { requests_in_flight: usize },
-    Saved(DateTime<Local>),
+impl From<graphql::GraphQLError> for Error {
+    fn from(graphql_error: graphql::GraphQLError) -> Self {
+        Self::GraphQL(graphql_error)
+    }
+}
+
+impl Notice for Error {
+    fn summary(&self) -> String {
+        match self {
+            Self::Auth(AuthError(message)) => message.clone(),
+            Self::GraphQL(graphql_error) => graphql_error.to_string(),
+        }
+    }
+
+    fn details(&self) -> Option<String> {
+        match self {
+            Self::Auth(_) => None,
+            Self::GraphQL(graphql_error) => Some(graphql_error.details()),
+        }
+    }
 }
 
 struct Form {
@@ -47,6 +145,19 @@ struct Form {
     errors: FormErrors,
 }
 
+impl Form {
+    fn validate(&mut self) -> bool {
+        self.errors = FormErrors {
+            username: validate_username(&self.username).err(),
+            email: validate_email(&self.email).err(),
+            password: validate_password(&self.password).err(),
+            confirm_password: validate_confirm_password(&self.password, &self.confirm_password)
+                .err(),
+        };
+        self.errors.is_empty()
+    }
+}
+
 #[derive(Default)]
 struct FormErrors {
     username: Option<String>,
@@ -55,13 +166,90 @@ struct FormErrors {
     confirm_password: Option<String>,
 }
 
+impl FormErrors {
+    fn is_empty(&self) -> bool {
+        self.username.is_none()
+            && self.email.is_none()
+            && self.password.is_none()
+            && self.confirm_password.is_none()
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AccountData {
+    client_ids: Vec<String>,
+    project_ids: Vec<String>,
+    time_entry_ids: Vec<String>,
+    time_block_ids: Vec<String>,
+    invoice_ids: Vec<String>,
+}
+
+// ------ Validation ------
+
+fn validate_username(username: &str) -> Result<(), String> {
+    if username.trim().is_empty() {
+        return Err("Username is required.".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_email(email: &str) -> Result<(), String> {
+    let invalid = || Err("Email is not valid.".to_owned());
+
+    if email.chars().any(char::is_whitespace) {
+        return invalid();
+    }
+    let mut parts = email.split('@');
+    let (local_part, domain) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(local_part), Some(domain), None) => (local_part, domain),
+        _ => return invalid(),
+    };
+    let domain_valid =
+        domain.contains('.') && domain.split('.').all(|label| not(label.is_empty()));
+
+    if local_part.is_empty() || not(domain_valid) {
+        return invalid();
+    }
+    Ok(())
+}
+
+/// An empty password means "keep the current password".
+fn validate_password(password: &str) -> Result<(), String> {
+    if password.is_empty() {
+        return Ok(());
+    }
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(format!(
+            "Password must have at least {} characters.",
+            MIN_PASSWORD_LENGTH
+        ));
+    }
+    let has_lowercase = password.chars().any(char::is_lowercase);
+    let has_uppercase = password.chars().any(char::is_uppercase);
+    let has_digit = password.chars().any(|character| character.is_ascii_digit());
+    if not(has_lowercase && has_uppercase && has_digit) {
+        return Err("Password must contain a lowercase letter, an uppercase letter and a digit."
+            .to_owned());
+    }
+    Ok(())
+}
+
+fn validate_confirm_password(password: &str, confirm_password: &str) -> Result<(), String> {
+    if password != confirm_password {
+        return Err("Passwords don't match.".to_owned());
+    }
+    Ok(())
+}
+
 // ------ ------
 //    Update
 // ------ ------
 
 pub enum Msg {
-    ChangesSaved(Option<FetchError>),
+    ChangesSaved(Option<Error>),
+    UserSaved(UserChanges, Option<Error>),
     ClearErrors,
+    DismissError(usize),
 
     UsernameChanged(String),
     EmailChanged(String),
@@ -69,22 +257,235 @@ pub enum Msg {
     ConfirmPasswordChanged(String),
 
     Save,
-    DeleteAccount
+
+    TimeZoneChanged(TimeZone),
+    ClockFormatChanged(ClockFormat),
+    DateFormatChanged(DateFormat),
+    WeekStartChanged(WeekStart),
+    DurationFormatChanged(DurationFormat),
+    SingleActiveTimerChanged(bool),
+    KeepDurationChanged(bool),
+    IdleThresholdChanged(Option<u32>),
+    PomodoroWorkMinutesChanged(u32),
+    PomodoroShortBreakMinutesChanged(u32),
+    PomodoroLongBreakMinutesChanged(u32),
+    PomodoroWorkIntervalsChanged(u32),
+    WorkingHoursRemindersChanged(bool),
+    ReminderAfterMinutesChanged(u32),
+    /// Day index from Monday.
+    WorkingDayChanged(usize, Option<WorkingDay>),
+
+    DeleteAccountConfirmationChanged(String),
+    DeleteAccount,
+    AccountDataFetched(graphql::Result<AccountData>),
+    AccountDeleted(Option<Error>),
 }
 
-pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
+pub fn update(
+    msg: Msg,
+    model: &mut Model,
+    graphql_config: &graphql::Config,
+    auth_provider: &impl AuthProvider,
+    preferences: &mut Preferences,
+    orders: &mut impl Orders<Msg>,
+) {
     match msg {
-        Msg::ChangesSaved(None) => {},
-        Msg::ChangesSaved(Some(fetch_error)) => {},
-        Msg::ClearErrors => {},
+        Msg::ChangesSaved(None) => {
+            log!("Msg::ChangesSaved");
+            model.changes_status.request_finished(true);
+        },
+        Msg::ChangesSaved(Some(error)) => {
+            log!("Msg::ChangesSaved", error);
+            model.errors.push(error);
+            model.changes_status.request_finished(false);
+        },
+        Msg::UserSaved(changes, None) => {
+            log!("Msg::UserSaved");
+            if let Some(username) = changes.username {
+                model.saved_username = username;
+            }
+            if let Some(email) = changes.email {
+                model.saved_email = email;
+            }
+            model.changes_status.request_finished(true);
+        },
+        Msg::UserSaved(_, Some(error)) => {
+            log!("Msg::UserSaved", error);
+            model.errors.push(error);
+            model.changes_status.request_finished(false);
+        },
+        Msg::ClearErrors => {
+            model.errors.clear();
+        },
+        Msg::DismissError(index) => {
+            error_center::remove_group(&mut model.errors, index);
+        },
+
+        Msg::UsernameChanged(username) => {
+            model.form.errors.username = validate_username(&username).err();
+            model.form.username = username;
+        },
+        Msg::EmailChanged(email) => {
+            model.form.errors.email = validate_email(&email).err();
+            model.form.email = email;
+        },
+        Msg::PasswordChanged(password) => {
+            let form = &mut model.form;
+            form.errors.password = validate_password(&password).err();
+            if not(form.confirm_password.is_empty()) {
+                form.errors.confirm_password =
+                    validate_confirm_password(&password, &form.confirm_password).err();
+            }
+            form.password = password;
+        },
+        Msg::ConfirmPasswordChanged(confirm_password) => {
+            let form = &mut model.form;
+            form.errors.confirm_password =
+                validate_confirm_password(&form.password, &confirm_password).err();
+            form.confirm_password = confirm_password;
+        },
+
+        Msg::Save => {
+            if not(model.form.validate()) {
+                return;
+            }
+            let form = &model.form;
+            let changes = UserChanges {
+                username: Some(form.username.trim().to_owned())
+                    .filter(|username| username != &model.saved_username),
+                email: Some(form.email.clone()).filter(|email| email != &model.saved_email),
+                password: Some(form.password.clone()).filter(|password| not(password.is_empty())),
+            };
+            if changes.is_empty() {
+                return;
+            }
+            model.form.password.clear();
+            model.form.confirm_password.clear();
+
+            // The saved values are updated when the auth provider has accepted the changes.
+            model.changes_status.request_sent();
+            let update_user = auth_provider.update_user(changes.clone());
+            orders.perform_cmd(async move {
+                Msg::UserSaved(changes, update_user.await.err().map(Error::from))
+            });
+        },
+
+        Msg::TimeZoneChanged(time_zone) => {
+            preferences.time_zone = time_zone;
+            preferences.save();
+        },
+        Msg::ClockFormatChanged(clock_format) => {
+            preferences.clock_format = clock_format;
+            preferences.save();
+        },
+        Msg::DateFormatChanged(date_format) => {
+            preferences.date_format = date_format;
+            preferences.save();
+        },
+        Msg::WeekStartChanged(week_start) => {
+            preferences.week_start = week_start;
+            preferences.save();
+        },
+        Msg::DurationFormatChanged(duration_format) => {
+            preferences.duration_format = duration_format;
+            preferences.save();
+        },
+        Msg::SingleActiveTimerChanged(single_active_timer) => {
+            preferences.single_active_timer = single_active_timer;
+            preferences.save();
+        },
+        Msg::KeepDurationChanged(keep_duration) => {
+            preferences.keep_duration = keep_duration;
+            preferences.save();
+        },
+        Msg::IdleThresholdChanged(idle_threshold_minutes) => {
+            preferences.idle_threshold_minutes = idle_threshold_minutes;
+            preferences.save();
+        },
+        Msg::PomodoroWorkMinutesChanged(minutes) => {
+            preferences.pomodoro.work_minutes = minutes;
+            preferences.save();
+        },
+        Msg::PomodoroShortBreakMinutesChanged(minutes) => {
+            preferences.pomodoro.short_break_minutes = minutes;
+            preferences.save();
+        },
+        Msg::PomodoroLongBreakMinutesChanged(minutes) => {
+            preferences.pomodoro.long_break_minutes = minutes;
+            preferences.save();
+        },
+        Msg::PomodoroWorkIntervalsChanged(work_intervals) => {
+            preferences.pomodoro.work_intervals_before_long_break = work_intervals;
+            preferences.save();
+        },
+        Msg::WorkingHoursRemindersChanged(reminders) => {
+            preferences.working_hours.reminders = reminders;
+            preferences.save();
+            if reminders {
+                orders.perform_cmd(notification::request_permission());
+            }
+        },
+        Msg::ReminderAfterMinutesChanged(minutes) => {
+            preferences.working_hours.reminder_after_minutes = minutes;
+            preferences.save();
+        },
+        Msg::WorkingDayChanged(day, working_day) => {
+            if let Some(working_day) = working_day {
+                if working_day.end_minutes <= working_day.start_minutes {
+                    let error = "The working day has to end after it starts.".to_owned();
+                    model.working_day_error = Some((day, error));
+                    return;
+                }
+            }
+            model.working_day_error = None;
+            if let Some(day) = preferences.working_hours.days.get_mut(day) {
+                *day = working_day;
+                preferences.save();
+            }
+        },
 
-        Msg::UsernameChanged(username) => {},
-        Msg::EmailChanged(email) => {},
-        Msg::PasswordChanged(password) => {},
-        Msg::ConfirmPasswordChanged(confirm_password) => {},
+        Msg::DeleteAccountConfirmationChanged(confirmation) => {
+            model.delete_account_confirmation = confirmation;
+        },
+        Msg::DeleteAccount => {
+            if model.delete_account_confirmation != DELETE_ACCOUNT_CONFIRMATION {
+                return;
+            }
+            let graphql_config = graphql_config.clone();
+            orders.perform_cmd(async move {
+                Msg::AccountDataFetched(request_account_data(&graphql_config).await)
+            });
+        },
+        Msg::AccountDataFetched(Ok(account_data)) => {
+            model.delete_account_confirmation.clear();
 
-        Msg::Save => {},
-        Msg::DeleteAccount => {},
+            let deletes = graphql::mutations::Deletes {
+                invoice_ids: account_data.invoice_ids,
+                time_block_ids: account_data.time_block_ids,
+                time_entry_ids: account_data.time_entry_ids,
+                project_ids: account_data.project_ids,
+                client_ids: account_data.client_ids,
+            };
+            let graphql_config = graphql_config.clone();
+            model.changes_status.request_sent();
+            orders.perform_cmd(async move {
+                Msg::AccountDeleted(
+                    delete_account(&graphql_config, deletes)
+                        .await
+                        .err()
+                        .map(Error::from),
+                )
+            });
+        },
+        Msg::AccountDeleted(error) => {
+            // The store still contains the deleted entities.
+            orders
+                .notify(store::Invalidate)
+                .send_msg(Msg::ChangesSaved(error));
+        },
+        Msg::AccountDataFetched(Err(graphql_error)) => {
+            model.errors.push(Error::from(graphql_error));
+        },
     }
 }
 
@@ -92,6 +493,384 @@ pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
 //     View
 // ------ ------
 
-pub fn view(model: &Model) -> Node<Msg> {
-    div!["Settings view"]
+pub fn view(model: &Model, preferences: &Preferences) -> Node<Msg> {
+    section![
+        h1![
+            C!["title", "ml-6", "my-6"],
+            "Settings",
+            changes::view_status(&model.changes_status, preferences),
+        ],
+        div![
+            C!["columns", "is-centered"],
+            div![
+                C!["column", "is-half"],
+                view_form(&model.form),
+                view_preferences(preferences, model.working_day_error.as_ref()),
+                view_delete_account(&model.delete_account_confirmation),
+            ]
+        ]
+    ]
+}
+
+pub fn view_errors(model: &Model) -> Node<Msg> {
+    error_center::view(&model.errors, Msg::DismissError, None, || Msg::ClearErrors)
+}
+
+fn view_form(form: &Form) -> Node<Msg> {
+    form![
+        C!["box"],
+        ev(Ev::Submit, |event| {
+            event.prevent_default();
+            Msg::Save
+        }),
+        view_field(
+            "Username",
+            "text",
+            &form.username,
+            form.errors.username.as_ref(),
+            Msg::UsernameChanged,
+        ),
+        view_field(
+            "Email",
+            "email",
+            &form.email,
+            form.errors.email.as_ref(),
+            Msg::EmailChanged,
+        ),
+        view_field(
+            "New Password",
+            "password",
+            &form.password,
+            form.errors.password.as_ref(),
+            Msg::PasswordChanged,
+        ),
+        view_field(
+            "Confirm Password",
+            "password",
+            &form.confirm_password,
+            form.errors.confirm_password.as_ref(),
+            Msg::ConfirmPasswordChanged,
+        ),
+        div![
+            C!["field"],
+            div![
+                C!["control"],
+                button![
+                    C!["button", "is-primary"],
+                    attrs! {
+                        At::Type => "submit",
+                        At::Disabled => not(form.errors.is_empty()).as_at_value(),
+                    },
+                    "Save",
+                ],
+            ],
+        ],
+    ]
+}
+
+fn view_field(
+    label_text: &str,
+    input_type: &str,
+    value: &str,
+    error: Option<&String>,
+    on_input: impl FnOnce(String) -> Msg + Clone + 'static,
+) -> Node<Msg> {
+    div![
+        C!["field"],
+        label![C!["label"], label_text],
+        div![
+            C!["control"],
+            input![
+                C!["input", IF!(error.is_some() => "is-danger")],
+                attrs! {
+                    At::Type => input_type,
+                    At::Value => value,
+                },
+                input_ev(Ev::Input, on_input),
+            ],
+        ],
+        error.map(|error| p![C!["help", "is-danger"], error]),
+    ]
+}
+
+fn view_preferences(
+    preferences: &Preferences,
+    working_day_error: Option<&(usize, String)>,
+) -> Node<Msg> {
+    let first_day = preferences.first_day_of_week().num_days_from_monday() as usize;
+    div![
+        C!["box", "mt-6"],
+        h2![C!["subtitle"], "Preferences"],
+        view_select(
+            "Time Zone",
+            TimeZone::options().map(|time_zone| (time_zone, time_zone.label())),
+            preferences.time_zone,
+            Msg::TimeZoneChanged,
+        ),
+        view_select(
+            "Clock",
+            ClockFormat::ALL.iter().map(|format| (*format, format.label().to_owned())),
+            preferences.clock_format,
+            Msg::ClockFormatChanged,
+        ),
+        view_select(
+            "Date Format",
+            DateFormat::ALL.iter().map(|format| (*format, format.label().to_owned())),
+            preferences.date_format,
+            Msg::DateFormatChanged,
+        ),
+        view_select(
+            "First Day of Week",
+            WeekStart::ALL.iter().map(|day| (*day, day.label().to_owned())),
+            preferences.week_start,
+            Msg::WeekStartChanged,
+        ),
+        view_select(
+            "Duration Display",
+            DurationFormat::ALL.iter().map(|format| (*format, format.label().to_owned())),
+            preferences.duration_format,
+            Msg::DurationFormatChanged,
+        ),
+        view_checkbox(
+            "Starting a timer stops the running one",
+            preferences.single_active_timer,
+            Msg::SingleActiveTimerChanged,
+        ),
+        view_checkbox(
+            "Editing the start keeps the duration",
+            preferences.keep_duration,
+            Msg::KeepDurationChanged,
+        ),
+        view_select(
+            "Ask About Idle Time After",
+            std::iter::once((None, "Never".to_owned())).chain(
+                IDLE_THRESHOLD_MINUTES
+                    .iter()
+                    .map(|minutes| (Some(*minutes), format!("{} minutes", minutes))),
+            ),
+            preferences.idle_threshold_minutes,
+            Msg::IdleThresholdChanged,
+        ),
+        h3![C!["subtitle", "is-6", "mt-5", "mb-3"], "Focus Mode"],
+        view_select(
+            "Work Interval",
+            minute_options((5..=90).step_by(5)),
+            preferences.pomodoro.work_minutes,
+            Msg::PomodoroWorkMinutesChanged,
+        ),
+        view_select(
+            "Short Break",
+            minute_options(1..=15),
+            preferences.pomodoro.short_break_minutes,
+            Msg::PomodoroShortBreakMinutesChanged,
+        ),
+        view_select(
+            "Long Break",
+            minute_options((5..=60).step_by(5)),
+            preferences.pomodoro.long_break_minutes,
+            Msg::PomodoroLongBreakMinutesChanged,
+        ),
+        view_select(
+            "Long Break After",
+            (2..=8).map(|work_intervals| (work_intervals, format!("{} work intervals", work_intervals))),
+            preferences.pomodoro.work_intervals_before_long_break,
+            Msg::PomodoroWorkIntervalsChanged,
+        ),
+        h3![C!["subtitle", "is-6", "mt-5", "mb-3"], "Working Hours"],
+        view_checkbox(
+            "Remind me to start and stop timers",
+            preferences.working_hours.reminders,
+            Msg::WorkingHoursRemindersChanged,
+        ),
+        view_select(
+            "Remind When No Timer Runs For",
+            minute_options((5..=60).step_by(5)),
+            preferences.working_hours.reminder_after_minutes,
+            Msg::ReminderAfterMinutesChanged,
+        ),
+        // The days are listed from the first day of the week.
+        (0..WEEKDAYS.len())
+            .map(|offset| (first_day + offset) % WEEKDAYS.len())
+            .map(|day| {
+                let error = working_day_error
+                    .filter(|(error_day, _)| *error_day == day)
+                    .map(|(_, error)| error.as_str());
+                view_working_day(day, WEEKDAYS[day], preferences.working_hours.days[day], error)
+            }),
+    ]
+}
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+fn view_working_day(
+    day: usize,
+    weekday: &str,
+    working_day: Option<WorkingDay>,
+    error: Option<&str>,
+) -> Node<Msg> {
+    // Half-hour steps, the working day can end at midnight (24:00).
+    let time_options = |last_half_hour| {
+        (0..=last_half_hour).map(|half_hours: u32| {
+            let minutes = half_hours * 30;
+            (minutes, format!("{:02}:{:02}", minutes / 60, minutes % 60))
+        })
+    };
+    div![
+        div![
+            C!["columns", "is-vcentered", "is-mobile", "mb-0"],
+            div![
+                C!["column", "is-one-third"],
+                view_checkbox(weekday, working_day.is_some(), move |works| {
+                    Msg::WorkingDayChanged(day, Some(WorkingDay::default()).filter(|_| works))
+                }),
+            ],
+            working_day.map(|working_day| {
+                vec![
+                    div![
+                        C!["column"],
+                        view_select(
+                            "From",
+                            time_options(47),
+                            working_day.start_minutes,
+                            move |start_minutes| {
+                                let working_day = WorkingDay { start_minutes, ..working_day };
+                                Msg::WorkingDayChanged(day, Some(working_day))
+                            },
+                        ),
+                    ],
+                    div![
+                        C!["column"],
+                        view_select(
+                            "To",
+                            time_options(48),
+                            working_day.end_minutes,
+                            move |end_minutes| {
+                                let working_day = WorkingDay { end_minutes, ..working_day };
+                                Msg::WorkingDayChanged(day, Some(working_day))
+                            },
+                        ),
+                    ],
+                ]
+            }),
+        ],
+        error.map(|error| p![C!["help", "is-danger", "mb-3"], error]),
+    ]
+}
+
+fn minute_options(minutes: impl Iterator<Item = u32>) -> impl Iterator<Item = (u32, String)> {
+    minutes.map(|minutes| (minutes, format!("{} minutes", minutes)))
+}
+
+fn view_checkbox(
+    label_text: &str,
+    checked: bool,
+    on_change: impl FnOnce(bool) -> Msg + Clone + 'static,
+) -> Node<Msg> {
+    div![
+        C!["field"],
+        div![
+            C!["control"],
+            label![
+                C!["checkbox"],
+                input![
+                    C!["mr-2"],
+                    attrs! {
+                        At::Type => "checkbox",
+                        At::Checked => checked.as_at_value(),
+                    },
+                    ev(Ev::Change, move |_| on_change(not(checked))),
+                ],
+                label_text,
+            ],
+        ],
+    ]
+}
+
+/// Option values are indices into `options` so any `Copy` value can be selected.
+fn view_select<T: Copy + PartialEq + 'static>(
+    label_text: &str,
+    options: impl Iterator<Item = (T, String)>,
+    selected: T,
+    on_change: impl FnOnce(T) -> Msg + Clone + 'static,
+) -> Node<Msg> {
+    let (values, labels): (Vec<_>, Vec<_>) = options.unzip();
+    let options: Vec<_> = labels
+        .iter()
+        .enumerate()
+        .map(|(index, label)| {
+            option![
+                attrs! {
+                    At::Value => index,
+                    At::Selected => (values[index] == selected).as_at_value(),
+                },
+                label,
+            ]
+        })
+        .collect();
+    div![
+        C!["field"],
+        label![C!["label"], label_text],
+        div![
+            C!["control"],
+            div![
+                C!["select"],
+                select![
+                    options,
+                    input_ev(Ev::Change, move |index| {
+                        index
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|index| values.get(index).copied())
+                            .map(on_change)
+                    }),
+                ],
+            ],
+        ],
+    ]
+}
+
+fn view_delete_account(confirmation: &str) -> Node<Msg> {
+    div![
+        C!["box", "mt-6"],
+        h2![C!["subtitle", "has-text-danger"], "Delete Account"],
+        p![
+            C!["mb-4"],
+            "All your clients, projects, time entries, time blocks and invoices will be deleted. Type ",
+            strong![DELETE_ACCOUNT_CONFIRMATION],
+            " to confirm.",
+        ],
+        div![
+            C!["field", "has-addons"],
+            div![
+                C!["control", "is-expanded"],
+                input![
+                    C!["input"],
+                    attrs! {
+                        At::Value => confirmation,
+                        At::Placeholder => DELETE_ACCOUNT_CONFIRMATION,
+                    },
+                    input_ev(Ev::Input, Msg::DeleteAccountConfirmationChanged),
+                ],
+            ],
+            div![
+                C!["control"],
+                button![
+                    C!["button", "is-danger"],
+                    attrs! {
+                        At::Disabled => (confirmation != DELETE_ACCOUNT_CONFIRMATION).as_at_value(),
+                    },
+                    ev(Ev::Click, |_| Msg::DeleteAccount),
+                    "Delete",
+                ],
+            ],
+        ],
+    ]
 }
@@ -1,15 +1,19 @@
 use seed::{prelude::*, *};
 
-use chrono::{prelude::*, Duration};
+use chrono::Duration;
 use ulid::Ulid;
 
-use cynic::QueryBuilder;
+use cynic::MutationBuilder;
 
 use std::collections::BTreeMap;
-use std::convert::identity;
 use std::ops::Add;
 
+use crate::changes::{self, Changes};
+use crate::duration;
 use crate::graphql;
+use crate::preferences::Preferences;
+use crate::remote_data::{self, RemoteData};
+use crate::store::{self, Snapshot, Store, TimeBlockStatus};
 
 const PRIMARY_COLOR: &str = "#00d1b2";
 
@@ -21,89 +25,69 @@ type TimeBlockId = Ulid;
 //     Init
 // ------ ------
 
-pub fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
-    orders.perform_cmd(async { Msg::ClientsFetched(request_clients().await) });
+pub fn init(url: Url, orders: &mut impl Orders<Msg>, store: &Store) -> Model {
     Model {
-        changes_status: ChangesStatus::NoChanges,
-        errors: Vec::new(),
+        changes: Changes::default(),
 
-        clients: RemoteData::Loading,
+        clients: clients_from_store(store),
+        store_changed_handle: orders
+            .subscribe_with_handle(|_: store::Changed| Msg::StoreChanged),
     }
 }
 
-async fn request_clients() -> graphql::Result<BTreeMap<ClientId, Client>> {
-    use graphql::queries::clients_with_time_blocks_and_time_entries as query_mod;
-
-    let invoice_mapper = |invoice: query_mod::Invoice| Invoice {
-        id: invoice.id.parse().expect("parse invoice Ulid"),
-        custom_id: invoice.custom_id,
-        url: invoice.url,
-    };
-
-    let status_mapper = |status: query_mod::TimeBlockStatus| match status {
-        query_mod::TimeBlockStatus::NonBillable => TimeBlockStatus::NonBillable,
-        query_mod::TimeBlockStatus::Unpaid => TimeBlockStatus::Unpaid,
-        query_mod::TimeBlockStatus::Paid => TimeBlockStatus::Paid,
-    };
-
-    let time_block_mapper = |time_block: query_mod::TimeBlock| {
-        (
-            time_block.id.parse().expect("parse time_block Ulid"),
-            TimeBlock {
-                name: time_block.name,
-                status: status_mapper(time_block.status),
-                duration: Duration::seconds(i64::from(time_block.duration)),
-                duration_change: None,
-                invoice: time_block.invoice.map(invoice_mapper),
-            },
-        )
-    };
+fn clients_from_store(store: &Store) -> RemoteData<BTreeMap<ClientId, Client>> {
+    store.remote_data().map(|store| {
+        let invoice_mapper = |invoice_id: InvoiceId| {
+            let invoice = store.invoices.get(&invoice_id)?;
+            Some(Invoice {
+                id: invoice_id,
+                custom_id: invoice.custom_id.clone(),
+                url: invoice.url.clone(),
+                url_error: None,
+            })
+        };
 
-    let compute_tracked_time = |projects: Vec<query_mod::Project>| {
-        projects
-            .into_iter()
-            .flat_map(|project| project.time_entries)
-            .map(|time_entry| {
-                let started: DateTime<Local> = time_entry
-                    .started
-                    .0
-                    .parse()
-                    .expect("parse time_entry started");
-
-                let stopped: DateTime<Local> = if let Some(stopped) = time_entry.stopped {
-                    stopped.0.parse().expect("parse time_entry stopped")
-                } else {
-                    chrono::Local::now()
-                };
+        let time_block_mapper = |(time_block_id, time_block): (TimeBlockId, &store::TimeBlock)| {
+            (
+                time_block_id,
+                TimeBlock {
+                    name: time_block.name.clone(),
+                    status: time_block.status,
+                    duration: time_block.duration,
+                    duration_change: None,
+                    duration_error: None,
+                    invoice: time_block.invoice_id.and_then(invoice_mapper),
+                },
+            )
+        };
 
-                stopped - started
-            })
-            .fold(Duration::seconds(0), Duration::add)
-    };
+        let compute_tracked_time = |client_id: ClientId| {
+            store
+                .projects_of(client_id)
+                .flat_map(|(project_id, _)| store.time_entries_of(project_id))
+                .map(|(_, time_entry)| {
+                    let stopped = time_entry.stopped.unwrap_or_else(chrono::Local::now);
+                    stopped - time_entry.started
+                })
+                .fold(Duration::seconds(0), Duration::add)
+        };
 
-    let client_mapper = |client: query_mod::Client| {
-        (
-            client.id.parse().expect("parse client Ulid"),
-            Client {
-                name: client.name,
-                time_blocks: client
-                    .time_blocks
-                    .into_iter()
-                    .map(time_block_mapper)
-                    .collect(),
-                tracked: compute_tracked_time(client.projects),
-            },
-        )
-    };
+        let client_mapper = |(client_id, client): (&ClientId, &store::Client)| {
+            (
+                *client_id,
+                Client {
+                    name: client.name.clone(),
+                    time_blocks: store
+                        .time_blocks_of(*client_id)
+                        .map(time_block_mapper)
+                        .collect(),
+                    tracked: compute_tracked_time(*client_id),
+                },
+            )
+        };
 
-    Ok(graphql::send_operation(query_mod::Query::build(()))
-        .await?
-        .query_client
-        .expect("get clients")
-        .into_iter()
-        .filter_map(identity)
-        .map(client_mapper)
-        .collect())
+        store.clients.iter().map(client_mapper).collect()
+    })
 }
 
 // ------ ------
@@ -111,40 +95,10 @@ async fn request_clients() -> graphql::Result<BTreeMap<ClientId, Client>> {
 // ------ ------
 
 pub struct Model {
-    changes_status: ChangesStatus,
-    errors: Vec<graphql::GraphQLError>,
+    changes: Changes,
 
     clients: RemoteData<BTreeMap<ClientId, Client>>,
-}
-
-enum RemoteData<T> {
-    NotAsked,
-    Loading,
-    Loaded(T),
-}
-
-impl<T> RemoteData<T> {
-    fn loaded(&self) -> Option<&T> {
-        if let Self::Loaded(data) = self {
-            Some(data)
-        } else {
-            None
-        }
-    }
-
-    fn loaded_mut(&mut self) -> Option<&mut T> {
-        if let Self::Loaded(data) = self {
-            Some(data)
-        } else {
-            None
-        }
-    }
-}
-
-enum ChangesStatus {
-    NoChanges,
-    Saving { requests_in_flight: usize },
-    Saved(DateTime<Local>),
+    store_changed_handle: SubHandle,
 }
 
 #[derive(Debug)]
@@ -160,14 +114,18 @@ struct TimeBlock {
     status: TimeBlockStatus,
     duration: Duration,
     duration_change: Option<String>,
+    duration_error: Option<String>,
     invoice: Option<Invoice>,
 }
 
-#[derive(Debug, Copy, Clone)]
-pub enum TimeBlockStatus {
-    NonBillable,
-    Unpaid,
-    Paid,
+impl From<TimeBlockStatus> for graphql::types::TimeBlockStatus {
+    fn from(status: TimeBlockStatus) -> Self {
+        match status {
+            TimeBlockStatus::NonBillable => Self::NonBillable,
+            TimeBlockStatus::Unpaid => Self::Unpaid,
+            TimeBlockStatus::Paid => Self::Paid,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -175,6 +133,7 @@ struct Invoice {
     id: InvoiceId,
     custom_id: Option<String>,
     url: Option<String>,
+    url_error: Option<String>,
 }
 
 // ------ ------
@@ -182,9 +141,9 @@ struct Invoice {
 // ------ ------
 
 pub enum Msg {
-    ClientsFetched(graphql::Result<BTreeMap<ClientId, Client>>),
-    ChangesSaved(Option<FetchError>),
-    ClearErrors,
+    StoreChanged,
+    RetryStoreFetch,
+    Changes(changes::Msg),
 
     // ------ TimeBlock ------
     AddTimeBlock(ClientId),
@@ -208,39 +167,155 @@ pub enum Msg {
     SaveInvoiceUrl(ClientId, TimeBlockId),
 }
 
-pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
+pub fn update(
+    msg: Msg,
+    model: &mut Model,
+    graphql_config: &graphql::Config,
+    store: &mut Store,
+    orders: &mut impl Orders<Msg>,
+) {
     match msg {
-        Msg::ClientsFetched(Ok(clients)) => {
-            log!("Msg::ClientsFetched", clients);
-            model.clients = RemoteData::Loaded(clients);
-        }
-        Msg::ClientsFetched(Err(graphql_error)) => {
-            model.errors.push(graphql_error);
+        Msg::StoreChanged => model.clients = clients_from_store(store),
+        Msg::RetryStoreFetch => {
+            model.clients = RemoteData::Loading;
+            orders.notify(store::Invalidate);
         }
 
-        Msg::ChangesSaved(None) => {
-            log!("Msg::ChangesSaved");
-        }
-        Msg::ChangesSaved(Some(fetch_error)) => {
-            log!("Msg::ChangesSaved", fetch_error);
+        Msg::Changes(msg) => {
+            let failed_change =
+                changes::update(msg, &mut model.changes, &mut orders.proxy(Msg::Changes));
+            if let Some(change) = failed_change {
+                // Undo the optimistic update, `StoreChanged` rebuilds the page.
+                store.revert(change);
+                orders.notify(store::Changed);
+            }
         }
 
-        Msg::ClearErrors => {}
-
         // ------ TimeBlock ------
         Msg::AddTimeBlock(client_id) => {
+            use graphql::mutations::add_time_block as mutation_mod;
+
             log!("Msg::AddTimeBlock", client_id);
+            let client = model
+                .clients
+                .loaded_mut()
+                .and_then(|clients| clients.get_mut(&client_id));
+
+            if let Some(client) = client {
+                let time_block_id = TimeBlockId::new();
+                let time_block = TimeBlock {
+                    name: String::new(),
+                    status: TimeBlockStatus::Unpaid,
+                    duration: Duration::seconds(0),
+                    duration_change: None,
+                    duration_error: None,
+                    invoice: None,
+                };
+                let input = mutation_mod::AddTimeBlockInput {
+                    id: time_block_id.to_string(),
+                    name: time_block.name.clone(),
+                    status: time_block.status.into(),
+                    duration: 0,
+                    client: graphql::types::ClientRef {
+                        id: Some(client_id.to_string()),
+                    },
+                };
+                let before = Snapshot::default().time_block(store, time_block_id);
+                store.time_blocks.insert(
+                    time_block_id,
+                    store::TimeBlock {
+                        client_id,
+                        name: time_block.name.clone(),
+                        status: time_block.status,
+                        duration: time_block.duration,
+                        invoice_id: None,
+                    },
+                );
+                client.time_blocks.insert(time_block_id, time_block);
+
+                let operation =
+                    mutation_mod::Mutation::build(mutation_mod::Arguments { input: vec![input] });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
         }
         Msg::DeleteTimeBlock(client_id, time_block_id) => {
             log!("Msg::DeleteTimeBlock", client_id, time_block_id);
+            let mut delete_time_block = move |client_id, time_block_id| -> Option<()> {
+                let time_blocks = &mut model.clients.loaded_mut()?.get_mut(&client_id)?.time_blocks;
+                let time_block = time_blocks.get(&time_block_id)?;
+
+                if let Ok(true) = window()
+                    .confirm_with_message(&format!("Time block \"{}\" will be deleted.", time_block.name))
+                {
+                    let invoice_ids = time_block
+                        .invoice
+                        .as_ref()
+                        .map(|invoice| invoice.id.to_string())
+                        .into_iter()
+                        .collect();
+                    time_blocks.remove(&time_block_id);
+                    let change = store.remove_time_block(time_block_id);
+
+                    let deletes = graphql::mutations::Deletes {
+                        invoice_ids,
+                        time_block_ids: vec![time_block_id.to_string()],
+                        ..Default::default()
+                    };
+                    if let Some(operation) = deletes.mutation() {
+                        model.changes.save(
+                            graphql_config,
+                            &mut orders.proxy(Msg::Changes),
+                            operation,
+                            change,
+                        );
+                    }
+                }
+                Some(())
+            };
+            delete_time_block(client_id, time_block_id);
         }
         Msg::SetTimeBlockStatus(client_id, time_block_id, time_block_status) => {
+            use graphql::mutations::update_time_block as mutation_mod;
+
             log!(
                 "Msg::SetTimeBlockStatus",
                 client_id,
                 time_block_id,
                 time_block_status
             );
+            let time_block = model
+                .clients
+                .loaded_mut()
+                .and_then(|clients| clients.get_mut(&client_id))
+                .and_then(|client| client.time_blocks.get_mut(&time_block_id));
+
+            if let Some(time_block) = time_block {
+                time_block.status = time_block_status;
+                let before = Snapshot::default().time_block(store, time_block_id);
+                if let Some(stored_time_block) = store.time_blocks.get_mut(&time_block_id) {
+                    stored_time_block.status = time_block_status;
+                }
+
+                let input = mutation_mod::UpdateTimeBlockInput::new(
+                    time_block_id,
+                    mutation_mod::TimeBlockPatch {
+                        status: Some(time_block_status.into()),
+                        ..Default::default()
+                    },
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
         }
 
         Msg::TimeBlockNameChanged(client_id, time_block_id, name) => {
@@ -259,7 +334,35 @@ pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
             set_time_block_name(name);
         }
         Msg::SaveTimeBlockName(client_id, time_block_id) => {
+            use graphql::mutations::update_time_block as mutation_mod;
+
             log!("Msg::SaveTimeBlockName", client_id, time_block_id);
+            let time_block = model
+                .clients
+                .loaded()
+                .and_then(|clients| clients.get(&client_id))
+                .and_then(|client| client.time_blocks.get(&time_block_id));
+
+            if let Some(time_block) = time_block {
+                let before = Snapshot::default().time_block(store, time_block_id);
+                if let Some(stored_time_block) = store.time_blocks.get_mut(&time_block_id) {
+                    stored_time_block.name = time_block.name.clone();
+                }
+                let input = mutation_mod::UpdateTimeBlockInput::new(
+                    time_block_id,
+                    mutation_mod::TimeBlockPatch {
+                        name: Some(time_block.name.clone()),
+                        ..Default::default()
+                    },
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
         }
 
         Msg::TimeBlockDurationChanged(client_id, time_block_id, duration) => {
@@ -283,27 +386,130 @@ pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
             set_time_block_duration_change(duration);
         }
         Msg::SaveTimeBlockDuration(client_id, time_block_id) => {
-            let mut set_time_block_duration_change = move || -> Option<()> {
-                Some(
-                    model
-                        .clients
-                        .loaded_mut()?
-                        .get_mut(&client_id)?
-                        .time_blocks
-                        .get_mut(&time_block_id)?
-                        .duration_change = None,
-                )
-            };
+            use graphql::mutations::update_time_block as mutation_mod;
+
             log!("Msg::SaveTimeBlockDuration", client_id, time_block_id);
-            set_time_block_duration_change();
+            let time_block = model
+                .clients
+                .loaded_mut()
+                .and_then(|clients| clients.get_mut(&client_id))
+                .and_then(|client| client.time_blocks.get_mut(&time_block_id));
+
+            if let Some(time_block) = time_block {
+                let duration = match time_block.duration_change.as_deref().map(parse_block_duration) {
+                    Some(Ok(duration)) => duration,
+                    Some(Err(error)) => {
+                        time_block.duration_error = Some(error);
+                        return;
+                    }
+                    None => return,
+                };
+                time_block.duration = duration;
+                time_block.duration_change = None;
+                time_block.duration_error = None;
+                let before = Snapshot::default().time_block(store, time_block_id);
+                if let Some(stored_time_block) = store.time_blocks.get_mut(&time_block_id) {
+                    stored_time_block.duration = duration;
+                }
+
+                let input = mutation_mod::UpdateTimeBlockInput::new(
+                    time_block_id,
+                    mutation_mod::TimeBlockPatch {
+                        duration: Some(duration.num_seconds() as i32),
+                        ..Default::default()
+                    },
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
         }
 
         // ------ Invoice ------
         Msg::AttachInvoice(client_id, time_block_id) => {
+            use graphql::mutations::add_invoice as mutation_mod;
+
             log!("Msg::AttachInvoice", client_id, time_block_id);
+            let time_block = model
+                .clients
+                .loaded_mut()
+                .and_then(|clients| clients.get_mut(&client_id))
+                .and_then(|client| client.time_blocks.get_mut(&time_block_id));
+
+            if let Some(time_block) = time_block {
+                let invoice_id = InvoiceId::new();
+                time_block.invoice = Some(Invoice {
+                    id: invoice_id,
+                    custom_id: None,
+                    url: None,
+                    url_error: None,
+                });
+                let before = Snapshot::default()
+                    .time_block(store, time_block_id)
+                    .invoice(store, invoice_id);
+                store.invoices.insert(
+                    invoice_id,
+                    store::Invoice {
+                        time_block_id,
+                        custom_id: None,
+                        url: None,
+                    },
+                );
+                if let Some(stored_time_block) = store.time_blocks.get_mut(&time_block_id) {
+                    stored_time_block.invoice_id = Some(invoice_id);
+                }
+
+                let input = mutation_mod::AddInvoiceInput {
+                    id: invoice_id.to_string(),
+                    custom_id: None,
+                    url: None,
+                    time_block: graphql::types::TimeBlockRef {
+                        id: Some(time_block_id.to_string()),
+                    },
+                };
+                let operation =
+                    mutation_mod::Mutation::build(mutation_mod::Arguments { input: vec![input] });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
         }
         Msg::DeleteInvoice(client_id, time_block_id) => {
+            use graphql::mutations::delete_invoices as mutation_mod;
+
             log!("Msg::DeleteInvoice", client_id, time_block_id);
+            let time_block = model
+                .clients
+                .loaded_mut()
+                .and_then(|clients| clients.get_mut(&client_id))
+                .and_then(|client| client.time_blocks.get_mut(&time_block_id));
+
+            if let Some(time_block) = time_block {
+                if let Ok(true) = window().confirm_with_message(&format!(
+                    "Invoice of time block \"{}\" will be deleted.",
+                    time_block.name
+                )) {
+                    if let Some(invoice) = time_block.invoice.take() {
+                        let change = store.remove_invoice(invoice.id);
+                        let operation = mutation_mod::Mutation::build(mutation_mod::Arguments {
+                            filter: graphql::types::InvoiceFilter::id(invoice.id),
+                        });
+                        model.changes.save(
+                            graphql_config,
+                            &mut orders.proxy(Msg::Changes),
+                            operation,
+                            change,
+                        );
+                    }
+                }
+            }
         }
 
         Msg::InvoiceCustomIdChanged(client_id, time_block_id, custom_id) => {
@@ -329,7 +535,36 @@ pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
             set_invoice_custom_id(custom_id);
         }
         Msg::SaveInvoiceCustomId(client_id, time_block_id) => {
+            use graphql::mutations::update_invoice as mutation_mod;
+
             log!("Msg::SaveInvoiceCustomId", client_id, time_block_id);
+            let invoice = model
+                .clients
+                .loaded()
+                .and_then(|clients| clients.get(&client_id))
+                .and_then(|client| client.time_blocks.get(&time_block_id))
+                .and_then(|time_block| time_block.invoice.as_ref());
+
+            if let Some(invoice) = invoice {
+                let before = Snapshot::default().invoice(store, invoice.id);
+                if let Some(stored_invoice) = store.invoices.get_mut(&invoice.id) {
+                    stored_invoice.custom_id = invoice.custom_id.clone();
+                }
+                let input = mutation_mod::UpdateInvoiceInput::new(
+                    invoice.id,
+                    mutation_mod::InvoicePatch {
+                        custom_id: Some(invoice.custom_id.clone().unwrap_or_default()),
+                        ..Default::default()
+                    },
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
         }
 
         Msg::InvoiceUrlChanged(client_id, time_block_id, url) => {
@@ -350,74 +585,124 @@ pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
             set_invoice_url(url);
         }
         Msg::SaveInvoiceUrl(client_id, time_block_id) => {
+            use graphql::mutations::update_invoice as mutation_mod;
+
             log!("Msg::SaveInvoiceUrl", client_id, time_block_id);
+            let invoice = model
+                .clients
+                .loaded_mut()
+                .and_then(|clients| clients.get_mut(&client_id))
+                .and_then(|client| client.time_blocks.get_mut(&time_block_id))
+                .and_then(|time_block| time_block.invoice.as_mut());
+
+            if let Some(invoice) = invoice {
+                let url = invoice.url.clone().unwrap_or_default();
+                if not(url.is_empty() || is_valid_url(&url)) {
+                    invoice.url_error = Some(format!("\"{}\" is not a valid http(s) URL.", url));
+                    return;
+                }
+                invoice.url_error = None;
+                let before = Snapshot::default().invoice(store, invoice.id);
+                if let Some(stored_invoice) = store.invoices.get_mut(&invoice.id) {
+                    stored_invoice.url = Some(url.clone());
+                }
+
+                let input = mutation_mod::UpdateInvoiceInput::new(
+                    invoice.id,
+                    mutation_mod::InvoicePatch {
+                        url: Some(url),
+                        ..Default::default()
+                    },
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
         }
     }
 }
 
+fn is_valid_url(url: &str) -> bool {
+    web_sys::Url::new(url)
+        .map(|url| matches!(url.protocol().as_str(), "http:" | "https:"))
+        .unwrap_or_default()
+}
+
+/// Parses durations like "2.5", "2:30" or "2h30m" that fit into the schema's `Int` seconds.
+fn parse_block_duration(duration: &str) -> Result<Duration, String> {
+    let duration = duration::parse(duration).map_err(|error| error.to_string())?;
+    if duration < Duration::zero() {
+        return Err("Duration can't be negative.".to_owned());
+    }
+    Ok(duration)
+}
+
 // ------ ------
 //     View
 // ------ ------
 
-pub fn view(model: &Model) -> Node<Msg> {
+pub fn view(model: &Model, preferences: &Preferences) -> Node<Msg> {
     section![
         h1![C!["title", "ml-6", "mt-6", "mb-5"],
             "Time Blocks",
+            changes::view_status(&model.changes.status, preferences),
         ],
         div![C!["columns", "is-centered"],
             div![C!["column", "is-two-thirds"],
-                match &model.clients {
-                    RemoteData::NotAsked | RemoteData::Loading => {
-                        progress![C!["progress", "is-link", "mt-6"]].into_nodes()
-                    },
-                    RemoteData::Loaded(clients) => {
-                        clients.iter().rev().map(|(client_id, client)| view_client(*client_id, client)).collect()
-                    }
-                }
+                remote_data::view(&model.clients, || Msg::RetryStoreFetch, |clients| {
+                    clients.iter().rev().map(|(client_id, client)| view_client(*client_id, client, preferences)).collect()
+                })
             ]
         ]
     ]
 }
 
-fn view_client(client_id: ClientId, client: &Client) -> Node<Msg> {
+pub fn view_errors(model: &Model) -> Node<Msg> {
+    changes::view_errors(&model.changes).map_msg(Msg::Changes)
+}
+
+fn view_client(client_id: ClientId, client: &Client, preferences: &Preferences) -> Node<Msg> {
     div![C!["box", "has-background-link", "mt-6",],
         div![C!["level", "is-mobile"], style!{St::FlexWrap => "wrap", St::MarginBottom => 0},
             div![C!["is-size-3", "has-text-link-light", "mb-2"], 
                 &client.name,
             ],
-            view_statistics(client.time_blocks.values(), &client.tracked),
+            view_statistics(client.time_blocks.values(), &client.tracked, preferences),
         ],
         view_add_time_block_button(client_id),
-        client.time_blocks.iter().rev().map(|(time_block_id, time_block)| view_time_block(client_id, *time_block_id, time_block)),
+        client.time_blocks.iter().rev().map(|(time_block_id, time_block)| view_time_block(client_id, *time_block_id, time_block, preferences)),
     ]
 }
 
-fn view_statistics<'a>(time_blocks: impl Iterator<Item = &'a TimeBlock>, tracked: &Duration, ) -> Node<Msg> {
-    let mut blocked = 0.;
-    let mut unpaid_total = 0.;
-    let mut paid_total = 0.;
+fn view_statistics<'a>(time_blocks: impl Iterator<Item = &'a TimeBlock>, tracked: &Duration, preferences: &Preferences) -> Node<Msg> {
+    let mut blocked = Duration::zero();
+    let mut unpaid_total = Duration::zero();
+    let mut paid_total = Duration::zero();
 
     for time_block in time_blocks {
-        let hours = time_block.duration.num_minutes() as f64 / 60.;
-        blocked += hours;
+        let duration = time_block.duration;
+        blocked = blocked + duration;
 
         match time_block.status {
             TimeBlockStatus::NonBillable => (),
-            TimeBlockStatus::Unpaid => unpaid_total += hours,
-            TimeBlockStatus::Paid => paid_total += hours,
+            TimeBlockStatus::Unpaid => unpaid_total = unpaid_total + duration,
+            TimeBlockStatus::Paid => paid_total = paid_total + duration,
         };
     }
 
-    let tracked = tracked.num_minutes() as f64 / 60.;
-    let to_block = tracked - blocked;
+    let to_block = *tracked - blocked;
 
-    let pair = |key: &str, value: f64| {
+    let pair = |key: &str, value: Duration| {
         div![C!["is-flex"], style!{St::JustifyContent => "space-between"},
             span![
                 key
             ],
             span![style!{St::MarginLeft => rem(1)},
-                format!("{:.1}", value)
+                preferences.format_duration(&value)
             ],
         ]
     };
@@ -432,7 +717,7 @@ fn view_statistics<'a>(time_blocks: impl Iterator<Item = &'a TimeBlock>, tracked
         div![
             div![C!["box", "has-background-link", "has-text-link-light"],
                 style!{St::MarginBottom => 0},
-                pair("Tracked", tracked),
+                pair("Tracked", *tracked),
             ],
             div![C!["box", "has-background-link", "has-text-link-light"],
                 pair("To Block", to_block),
@@ -457,7 +742,7 @@ fn view_add_time_block_button(client_id: ClientId) -> Node<Msg> {
     ]
 }
 
-fn view_time_block(client_id: ClientId, time_block_id: TimeBlockId, time_block: &TimeBlock) -> Node<Msg> {
+fn view_time_block(client_id: ClientId, time_block_id: TimeBlockId, time_block: &TimeBlock, preferences: &Preferences) -> Node<Msg> {
     div![C!["box"],
         div![C!["level", "is-mobile"],
             input![C!["input", "is-size-4"], 
@@ -474,31 +759,31 @@ fn view_time_block(client_id: ClientId, time_block_id: TimeBlockId, time_block:
                 ev(Ev::Change, move |_| Msg::SaveTimeBlockName(client_id, time_block_id)),
             ],
             div![C!["is-flex"], style!{St::AlignItems => "center"},
-                input![C!["input", "is-size-4", "has-text-right"], 
+                input![C!["input", "is-size-4", "has-text-right", IF!(time_block.duration_error.is_some() => "is-danger")], 
                     style!{
                         St::BoxShadow => "none",
                         St::BackgroundColor => "transparent",
                         St::Height => rem(3),
                         St::Border => "none",
                         St::BorderBottom => format!("{} {} {}", "solid", PRIMARY_COLOR, px(2)),
-                        St::MaxWidth => rem(6),
+                        St::MaxWidth => rem(8),
                     },
                     attrs!{
                         At::Value => if let Some(duration) = &time_block.duration_change {
                             duration.to_owned()
                         } else {
-                            format!("{:.1}", time_block.duration.num_minutes() as f64 / 60.)
+                            preferences.format_duration(&time_block.duration)
                         }
                     },
                     input_ev(Ev::Input, move |duration| Msg::TimeBlockDurationChanged(client_id, time_block_id, duration)),
                     ev(Ev::Change, move |_| Msg::SaveTimeBlockDuration(client_id, time_block_id)),
                 ],
-                div![
-                    "h"
-                ],
             ],
             view_delete_button(move || Msg::DeleteTimeBlock(client_id, time_block_id)),
         ],
+        time_block.duration_error.as_ref().map(|error| {
+            p![C!["help", "is-danger", "has-text-right", "mb-3"], error]
+        }),
         div![C!["level", "is-mobile"],
             view_status_buttons(client_id, time_block_id, time_block.status),
             IF!(time_block.invoice.is_none() => view_attach_invoice_button(client_id, time_block_id)),
@@ -566,7 +851,7 @@ fn view_invoice(client_id: ClientId, time_block_id: TimeBlockId, invoice: &Invoi
         ],
         div![C!["level", "is-mobile"],
             div!["URL"],
-            input![C!["input", "has-text-link-light"], 
+            input![C!["input", "has-text-link-light", IF!(invoice.url_error.is_some() => "is-danger")], 
                 style!{
                     St::BoxShadow => "none",
                     St::BackgroundColor => "transparent",
@@ -578,8 +863,11 @@ fn view_invoice(client_id: ClientId, time_block_id: TimeBlockId, invoice: &Invoi
                 input_ev(Ev::Input, move |url| Msg::InvoiceUrlChanged(client_id, time_block_id, url)),
                 ev(Ev::Change, move |_| Msg::SaveInvoiceUrl(client_id, time_block_id)),
             ],
-            invoice.url.as_ref().map(move |url| view_go_button(url)),
+            invoice.url.as_ref().filter(|url| is_valid_url(url)).map(move |url| view_go_button(url)),
         ],
+        invoice.url_error.as_ref().map(|error| {
+            p![C!["help", "has-text-warning", "has-text-right"], error]
+        }),
     ]
 }
 
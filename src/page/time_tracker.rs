@@ -3,89 +3,110 @@ use seed::{prelude::*, *};
 use chrono::prelude::*;
 use ulid::Ulid;
 
-use cynic::QueryBuilder;
+use cynic::MutationBuilder;
 
-use std::collections::BTreeMap;
-use std::convert::identity;
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter;
 
+use crate::changes::{self, Changes};
+use crate::analysis;
+use crate::duration;
+use crate::focus_session::{FocusPhase, FocusSession};
 use crate::graphql;
+use crate::notification;
+use crate::preferences::Preferences;
+use crate::remote_data::{self, RemoteData};
+use crate::store::{self, Snapshot, Store};
+use crate::timer;
 
 const PRIMARY_COLOR: &str = "#00d1b2";
 const LINK_COLOR: &str = "#3273dc";
+pub const TITLE: &str = "Time Tracker";
+/// Entries with the same name and at most this gap can be merged.
+const MAX_MERGE_GAP_SECONDS: i64 = 60;
+const RECENT_TIME_ENTRIES: usize = 5;
+const FAVOURITES_STORAGE_KEY: &str = "favourite_time_entries";
 
 type ClientId = Ulid;
 type ProjectId = Ulid;
 type TimeEntryId = Ulid;
+/// Move targets labeled "Client / Project".
+type ProjectOptions = Vec<(ClientId, ProjectId, String)>;
+
+type TimeEntryWarnings = BTreeMap<TimeEntryId, Vec<(String, Option<analysis::Fix>)>>;
 
 // ------ ------
 //     Init
 // ------ ------
 
-pub fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
-    orders.perform_cmd(async { Msg::ClientsFetched(request_clients().await) });
-
+pub fn init(
+    url: Url,
+    orders: &mut impl Orders<Msg>,
+    store: &Store,
+    preferences: &Preferences,
+) -> Model {
+    let clients = clients_from_store(store);
     Model {
-        changes_status: ChangesStatus::NoChanges,
-        errors: Vec::new(),
-
-        clients: RemoteData::Loading,
-        timer_handle: orders.stream_with_handle(streams::interval(1000, || Msg::OnSecondTick)),
+        changes: Changes::default(),
+
+        derived_data: DerivedData::new(&clients, preferences),
+        clients,
+        selected_time_entries: BTreeSet::new(),
+        last_activity: chrono::Local::now(),
+        idle_since: None,
+        idle_prompt: None,
+        favourites: load_favourites(),
+        store_changed_handle: orders
+            .subscribe_with_handle(|_: store::Changed| Msg::StoreChanged),
+        activity_handles: vec![
+            orders.stream_with_handle(streams::window_event(Ev::MouseMove, |_| Msg::UserActivity)),
+            orders.stream_with_handle(streams::window_event(Ev::MouseDown, |_| Msg::UserActivity)),
+            orders.stream_with_handle(streams::window_event(Ev::KeyDown, |_| Msg::UserActivity)),
+            orders.stream_with_handle(streams::window_event(Ev::TouchStart, |_| Msg::UserActivity)),
+            orders.stream_with_handle(streams::window_event(Ev::Scroll, |_| Msg::UserActivity)),
+            // Both leaving and returning to the tab count as activity.
+            orders.stream_with_handle(streams::document_event(Ev::VisibilityChange, |_| {
+                Msg::UserActivity
+            })),
+        ],
     }
 }
 
-async fn request_clients() -> graphql::Result<BTreeMap<ClientId, Client>> {
-    use graphql::queries::clients_with_projects_with_time_entries as query_mod;
-
-    let time_entry_mapper = |time_entry: query_mod::TimeEntry| {
-        (
-            time_entry.id.parse().expect("parse time_entry Ulid"),
-            TimeEntry {
-                name: time_entry.name,
-                started: time_entry
-                    .started
-                    .0
-                    .parse()
-                    .expect("parse time_entry started time"),
-                stopped: time_entry
-                    .stopped
-                    .map(|time| time.0.parse().expect("parse time_entry started time")),
-                change: None,
-            },
-        )
-    };
-
-    let project_mapper = |project: query_mod::Project| {
-        (
-            project.id.parse().expect("parse project Ulid"),
-            Project {
-                name: project.name,
-                time_entries: project
-                    .time_entries
-                    .into_iter()
-                    .map(time_entry_mapper)
-                    .collect(),
-            },
-        )
-    };
+fn clients_from_store(store: &Store) -> RemoteData<BTreeMap<ClientId, Client>> {
+    store.remote_data().map(|store| {
+        let time_entry_mapper = |(time_entry_id, time_entry): (TimeEntryId, &store::TimeEntry)| {
+            (
+                time_entry_id,
+                TimeEntry::new(time_entry.name.clone(), time_entry.started, time_entry.stopped),
+            )
+        };
 
-    let client_mapper = |client: query_mod::Client| {
-        (
-            client.id.parse().expect("parse client Ulid"),
-            Client {
-                name: client.name,
-                projects: client.projects.into_iter().map(project_mapper).collect(),
-            },
-        )
-    };
+        let project_mapper = |(project_id, project): (ProjectId, &store::Project)| {
+            (
+                project_id,
+                Project {
+                    name: project.name.clone(),
+                    time_entries: store
+                        .time_entries_of(project_id)
+                        .map(time_entry_mapper)
+                        .collect(),
+                    new_time_entry_form: None,
+                },
+            )
+        };
+
+        let client_mapper = |(client_id, client): (&ClientId, &store::Client)| {
+            (
+                *client_id,
+                Client {
+                    name: client.name.clone(),
+                    projects: store.projects_of(*client_id).map(project_mapper).collect(),
+                },
+            )
+        };
 
-    Ok(graphql::send_operation(query_mod::Query::build(()))
-        .await?
-        .query_client
-        .expect("get clients")
-        .into_iter()
-        .filter_map(identity)
-        .map(client_mapper)
-        .collect())
+        store.clients.iter().map(client_mapper).collect()
+    })
 }
 
 // ------ ------
@@ -93,42 +114,78 @@ async fn request_clients() -> graphql::Result<BTreeMap<ClientId, Client>> {
 // ------ ------
 
 pub struct Model {
-    changes_status: ChangesStatus,
-    errors: Vec<graphql::GraphQLError>,
+    changes: Changes,
 
     clients: RemoteData<BTreeMap<ClientId, Client>>,
-    timer_handle: StreamHandle,
+    derived_data: DerivedData,
+    // Time entries selected for bulk actions.
+    selected_time_entries: BTreeSet<TimeEntryId>,
+    store_changed_handle: SubHandle,
+    last_activity: DateTime<Local>,
+    // Set by the timer once the user has been idle for too long.
+    idle_since: Option<DateTime<Local>>,
+    idle_prompt: Option<IdlePrompt>,
+    // Pinned (project, time entry name) pairs, stored in the local storage.
+    favourites: BTreeSet<(ProjectId, String)>,
+    activity_handles: Vec<StreamHandle>,
 }
 
-// ----- Remote Data -----
-enum RemoteData<T> {
-    NotAsked,
-    Loading,
-    Loaded(T),
+impl Drop for Model {
+    // Don't leave the elapsed time in the tab title on other pages.
+    fn drop(&mut self) {
+        document().set_title(TITLE);
+    }
 }
 
-impl<T> RemoteData<T> {
-    fn loaded(&self) -> Option<&T> {
-        if let Self::Loaded(data) = self {
-            Some(data)
-        } else {
-            None
-        }
-    }
+/// Data the view needs from `clients`, recomputed when they change instead of on every render.
+#[derive(Default)]
+struct DerivedData {
+    warnings: TimeEntryWarnings,
+    recent_time_entries: Vec<(ClientId, ProjectId, String)>,
+    project_options: ProjectOptions,
+    tracked_this_week: BTreeMap<ClientId, chrono::Duration>,
+    computed: Option<DateTime<Local>>,
+}
 
-    fn loaded_mut(&mut self) -> Option<&mut T> {
-        if let Self::Loaded(data) = self {
-            Some(data)
-        } else {
-            None
+impl DerivedData {
+    fn new(clients: &RemoteData<BTreeMap<ClientId, Client>>, preferences: &Preferences) -> Self {
+        let clients = match clients.loaded() {
+            Some(clients) => clients,
+            None => return Self::default(),
+        };
+        let now = chrono::Local::now();
+        Self {
+            warnings: time_entry_warnings(clients, preferences),
+            recent_time_entries: recent_time_entries(clients),
+            project_options: project_options(clients),
+            tracked_this_week: tracked_this_week(clients, now, preferences),
+            computed: Some(now),
         }
     }
 }
 
-enum ChangesStatus {
-    NoChanges,
-    Saving { requests_in_flight: usize },
-    Saved(DateTime<Local>),
+/// Asks the returning user what to do with the time tracked while idle.
+struct IdlePrompt {
+    idle_started: DateTime<Local>,
+    returned: DateTime<Local>,
+}
+
+fn load_favourites() -> BTreeSet<(ProjectId, String)> {
+    LocalStorage::get::<_, Vec<(String, String)>>(FAVOURITES_STORAGE_KEY)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(project_id, name)| Some((project_id.parse().ok()?, name)))
+        .collect()
+}
+
+fn save_favourites(favourites: &BTreeSet<(ProjectId, String)>) {
+    let favourites = favourites
+        .iter()
+        .map(|(project_id, name)| (project_id.to_string(), name.clone()))
+        .collect::<Vec<_>>();
+    if let Err(error) = LocalStorage::insert(FAVOURITES_STORAGE_KEY, &favourites) {
+        error!("Favourites cannot be saved!", error);
+    }
 }
 
 #[derive(Debug)]
@@ -141,14 +198,112 @@ pub struct Client {
 struct Project {
     name: String,
     time_entries: BTreeMap<Ulid, TimeEntry>,
+    new_time_entry_form: Option<NewTimeEntryForm>,
+}
+
+/// Form for time entries that weren't tracked with the Start button.
+#[derive(Debug)]
+struct NewTimeEntryForm {
+    name: String,
+    started_date: String,
+    started_time: String,
+    stopped_date: String,
+    stopped_time: String,
+    duration: String,
+    // Set `stopped` with `duration` instead of the date and time.
+    use_duration: bool,
+    error: Option<String>,
+    // Saving again confirms the overlap.
+    overlap_warning: Option<String>,
+}
+
+impl NewTimeEntryForm {
+    fn new(preferences: &Preferences) -> Self {
+        let stopped = chrono::Local::now();
+        let started = stopped - chrono::Duration::hours(1);
+        Self {
+            name: String::new(),
+            started_date: preferences.format_date(&started),
+            started_time: preferences.format_time(&started),
+            stopped_date: preferences.format_date(&stopped),
+            stopped_time: preferences.format_time(&stopped),
+            duration: preferences.format_duration(&(stopped - started)),
+            use_duration: false,
+            error: None,
+            overlap_warning: None,
+        }
+    }
+
+    /// Returns `started` and `stopped` parsed with the same rules as inline edits.
+    fn parse(
+        &self,
+        preferences: &Preferences,
+    ) -> Result<(DateTime<Local>, DateTime<Local>), String> {
+        let started = preferences.parse_date_time(&self.started_date, &self.started_time)?;
+        let stopped = if self.use_duration {
+            started + duration::parse(&self.duration).map_err(|error| error.to_string())?
+        } else {
+            preferences.parse_date_time(&self.stopped_date, &self.stopped_time)?
+        };
+        if stopped <= started {
+            return Err("The time entry has to stop after it started.".to_owned());
+        }
+        Ok((started, stopped))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum NewTimeEntryField {
+    Name,
+    StartedDate,
+    StartedTime,
+    StoppedDate,
+    StoppedTime,
+    Duration,
 }
 
 #[derive(Debug)]
 struct TimeEntry {
     name: String,
+    // The name being typed, it's saved when the input loses focus.
+    name_change: Option<String>,
     started: DateTime<Local>,
     stopped: Option<DateTime<Local>>,
     change: Option<TimeEntryChange>,
+    change_error: Option<String>,
+    split_form: Option<SplitForm>,
+}
+
+impl TimeEntry {
+    fn new(name: String, started: DateTime<Local>, stopped: Option<DateTime<Local>>) -> Self {
+        Self {
+            name,
+            name_change: None,
+            started,
+            stopped,
+            change: None,
+            change_error: None,
+            split_form: None,
+        }
+    }
+
+    /// The saved fields, without the unsaved changes.
+    fn to_stored(&self, project_id: ProjectId) -> store::TimeEntry {
+        store::TimeEntry {
+            project_id,
+            name: self.name.clone(),
+            started: self.started,
+            stopped: self.stopped,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SplitForm {
+    time: String,
+    // Name of the second entry.
+    name: String,
+    error: Option<String>,
 }
 
 #[derive(Debug)]
@@ -165,9 +320,9 @@ enum TimeEntryChange {
 // ------ ------
 
 pub enum Msg {
-    ClientsFetched(graphql::Result<BTreeMap<ClientId, Client>>),
-    ChangesSaved(Option<FetchError>),
-    ClearErrors,
+    StoreChanged,
+    RetryStoreFetch,
+    Changes(changes::Msg),
 
     Start(ClientId, ProjectId),
     Stop(ClientId, ProjectId),
@@ -193,35 +348,105 @@ pub enum Msg {
 
     SaveTimeEntryChange(ClientId, ProjectId, TimeEntryId),
 
+    OpenNewTimeEntryForm(ClientId, ProjectId),
+    CloseNewTimeEntryForm(ClientId, ProjectId),
+    NewTimeEntryFieldChanged(ClientId, ProjectId, NewTimeEntryField, String),
+    ToggleNewTimeEntryDuration(ClientId, ProjectId),
+    SaveNewTimeEntry(ClientId, ProjectId),
+
+    ApplyTimeEntryFix(ClientId, ProjectId, TimeEntryId, analysis::Fix),
+
+    OpenSplitForm(ClientId, ProjectId, TimeEntryId),
+    CloseSplitForm(ClientId, ProjectId, TimeEntryId),
+    SplitTimeChanged(ClientId, ProjectId, TimeEntryId, String),
+    SplitNameChanged(ClientId, ProjectId, TimeEntryId, String),
+    SplitTimeEntry(ClientId, ProjectId, TimeEntryId),
+    MergeWithPrevious(ClientId, ProjectId, TimeEntryId),
+
+    ToggleTimeEntrySelection(TimeEntryId),
+    ClearTimeEntrySelection,
+    MoveTimeEntries(Vec<TimeEntryId>, ClientId, ProjectId),
+
+    UserActivity,
+    KeepIdleTime,
+    DiscardIdleTime,
+    SplitIdleTime,
+
+    StartFocusSession(ClientId, ProjectId),
+    StopFocusSession,
+
+
+    ContinueTimeEntry(ClientId, ProjectId, String),
+    ToggleFavourite(ProjectId, String),
+
     OnSecondTick,
 }
 
-pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
-    match msg {
-        Msg::ClientsFetched(Ok(clients)) => {
-            log!("Msg::ClientsFetched", clients);
-            model.clients = RemoteData::Loaded(clients);
-        }
-        Msg::ClientsFetched(Err(graphql_error)) => {
-            model.errors.push(graphql_error);
-        }
+pub fn update(
+    msg: Msg,
+    model: &mut Model,
+    graphql_config: &graphql::Config,
+    preferences: &Preferences,
+    focus_session: &mut Option<FocusSession>,
+    store: &mut Store,
+    orders: &mut impl Orders<Msg>,
+) {
+    // Ticks and activity events don't change the time entries.
+    let clients_may_change = not(matches!(msg, Msg::OnSecondTick | Msg::UserActivity));
+    update_clients(msg, model, graphql_config, preferences, focus_session, store, orders);
+    if clients_may_change {
+        model.derived_data = DerivedData::new(&model.clients, preferences);
+    }
+}
 
-        Msg::ChangesSaved(None) => {
-            log!("Msg::ChangesSaved");
+fn update_clients(
+    msg: Msg,
+    model: &mut Model,
+    graphql_config: &graphql::Config,
+    preferences: &Preferences,
+    focus_session: &mut Option<FocusSession>,
+    store: &mut Store,
+    orders: &mut impl Orders<Msg>,
+) {
+    match msg {
+        Msg::StoreChanged => {
+            model.clients = clients_from_store(store);
+            model.selected_time_entries.clear();
         }
-        Msg::ChangesSaved(Some(fetch_error)) => {
-            log!("Msg::ChangesSaved", fetch_error);
+        Msg::RetryStoreFetch => {
+            model.clients = RemoteData::Loading;
+            orders.notify(store::Invalidate);
         }
 
-        Msg::ClearErrors => {
-            log!("Msg::ClearErrors");
+        Msg::Changes(msg) => {
+            let failed_change =
+                changes::update(msg, &mut model.changes, &mut orders.proxy(Msg::Changes));
+            if let Some(change) = failed_change {
+                // Undo the optimistic update only in the affected time entries,
+                // rebuilding the page would lose the unsaved edits and the selection.
+                let time_entry_ids = change.time_entry_ids().collect::<Vec<_>>();
+                store.revert(change);
+                for time_entry_id in &time_entry_ids {
+                    if not(store.time_entries.contains_key(time_entry_id)) {
+                        model.selected_time_entries.remove(time_entry_id);
+                    }
+                }
+                restore_time_entries(store, &mut model.clients, time_entry_ids);
+            }
         }
 
         Msg::Start(client_id, project_id) => {
             log!("Msg::Start", client_id, project_id);
+            let single_active_timer = preferences.single_active_timer;
+            let name = String::new();
+            let save = timer::start(store, graphql_config, project_id, name, single_active_timer);
+            save_timer(model, save, store, orders);
         }
         Msg::Stop(client_id, project_id) => {
             log!("Msg::Stop", client_id, project_id);
+            for save in timer::stop(store, graphql_config, project_id) {
+                save_timer(model, save, store, orders);
+            }
         }
 
         Msg::DeleteTimeEntry(client_id, project_id, time_entry_id) => {
@@ -239,7 +464,7 @@ pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
                         .get_mut(&project_id)?
                         .time_entries
                         .get_mut(&time_entry_id)?
-                        .name = name,
+                        .name_change = Some(name),
                 )
             };
             log!(
@@ -252,12 +477,41 @@ pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
             set_time_entry_name(name);
         }
         Msg::SaveTimeEntryName(client_id, project_id, time_entry_id) => {
+            use graphql::mutations::update_time_entry as mutation_mod;
+
             log!(
                 "Msg::SaveTimeEntryName",
                 client_id,
                 project_id,
                 time_entry_id
             );
+            let time_entry = model.clients.loaded_mut().and_then(|clients| {
+                find_time_entry_mut(clients, client_id, project_id, time_entry_id)
+            });
+
+            if let Some(time_entry) = time_entry {
+                let name = match time_entry.name_change.take() {
+                    Some(name) => name,
+                    None => return,
+                };
+                time_entry.name = name.clone();
+                let change = sync_time_entries(store, &model.clients, vec![time_entry_id]);
+
+                let input = graphql::types::UpdateTimeEntryInput::new(
+                    graphql::types::TimeEntryFilter::id(time_entry_id),
+                    graphql::types::TimeEntryPatch {
+                        name: Some(name),
+                        ..Default::default()
+                    },
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    change,
+                );
+            }
         }
 
         Msg::TimeEntryStartedChanged(client_id, project_id, time_entry_id, started) => {}
@@ -314,17 +568,19 @@ pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
 
         Msg::TimeEntryDurationChanged(client_id, project_id, time_entry_id, duration) => {
             let mut set_time_entry_change = move |change| -> Option<()> {
-                Some(
-                    model
-                        .clients
-                        .loaded_mut()?
-                        .get_mut(&client_id)?
-                        .projects
-                        .get_mut(&project_id)?
-                        .time_entries
-                        .get_mut(&time_entry_id)?
-                        .change = Some(change),
-                )
+                let time_entry = model
+                    .clients
+                    .loaded_mut()?
+                    .get_mut(&client_id)?
+                    .projects
+                    .get_mut(&project_id)?
+                    .time_entries
+                    .get_mut(&time_entry_id)?;
+                if let TimeEntryChange::Duration(duration) = &change {
+                    time_entry.change_error =
+                        duration::parse(duration).err().map(|error| error.to_string());
+                }
+                Some(time_entry.change = Some(change))
             };
             log!(
                 "Msg::TimeEntryDurationChanged",
@@ -384,76 +640,1483 @@ pub fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
         }
 
         Msg::SaveTimeEntryChange(client_id, project_id, time_entry_id) => {
-            let mut delete_time_entry_change = move || -> Option<()> {
-                Some(
-                    model
-                        .clients
-                        .loaded_mut()?
-                        .get_mut(&client_id)?
-                        .projects
-                        .get_mut(&project_id)?
-                        .time_entries
-                        .get_mut(&time_entry_id)?
-                        .change = None,
-                )
-            };
+            use graphql::mutations::update_time_entry as mutation_mod;
+
             log!(
                 "Msg::SaveTimeEntryChange",
                 client_id,
                 project_id,
                 time_entry_id
             );
-            delete_time_entry_change();
+            let clients = match model.clients.loaded_mut() {
+                Some(clients) => clients,
+                None => return,
+            };
+            let time_entry = match find_time_entry(clients, client_id, project_id, time_entry_id)
+            {
+                Some(time_entry) => time_entry,
+                None => return,
+            };
+            let change = match &time_entry.change {
+                Some(change) => change,
+                None => return,
+            };
+            let result = apply_time_entry_change(change, time_entry, preferences).and_then(
+                |(started, stopped)| {
+                    check_overlaps(clients, time_entry_id, started, stopped, preferences)?;
+                    Ok((started, stopped))
+                },
+            );
+
+            let time_entry =
+                match find_time_entry_mut(clients, client_id, project_id, time_entry_id) {
+                    Some(time_entry) => time_entry,
+                    None => return,
+                };
+
+            match result {
+                Ok((started, stopped)) => {
+                    time_entry.started = started;
+                    time_entry.stopped = stopped;
+                    time_entry.change = None;
+                    time_entry.change_error = None;
+                    let change = sync_time_entries(store, &model.clients, vec![time_entry_id]);
+
+                    let input = graphql::types::UpdateTimeEntryInput::new(
+                        graphql::types::TimeEntryFilter::id(time_entry_id),
+                        graphql::types::TimeEntryPatch {
+                            started: Some(graphql::types::DateTime(started.to_rfc3339())),
+                            stopped: stopped
+                                .map(|stopped| graphql::types::DateTime(stopped.to_rfc3339())),
+                            ..Default::default()
+                        },
+                    );
+                    let operation =
+                        mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                    model.changes.save(
+                        graphql_config,
+                        &mut orders.proxy(Msg::Changes),
+                        operation,
+                        change,
+                    );
+                }
+                // The invalid input stays in the field so the user can fix it.
+                Err(error) => time_entry.change_error = Some(error),
+            }
+        }
+
+        Msg::OpenNewTimeEntryForm(client_id, project_id) => {
+            if let Some(project) = find_project_mut(&mut model.clients, client_id, project_id) {
+                project.new_time_entry_form = Some(NewTimeEntryForm::new(preferences));
+            }
+        }
+        Msg::CloseNewTimeEntryForm(client_id, project_id) => {
+            if let Some(project) = find_project_mut(&mut model.clients, client_id, project_id) {
+                project.new_time_entry_form = None;
+            }
+        }
+        Msg::NewTimeEntryFieldChanged(client_id, project_id, field, value) => {
+            let form = find_project_mut(&mut model.clients, client_id, project_id)
+                .and_then(|project| project.new_time_entry_form.as_mut());
+
+            if let Some(form) = form {
+                let form_field = match field {
+                    NewTimeEntryField::Name => &mut form.name,
+                    NewTimeEntryField::StartedDate => &mut form.started_date,
+                    NewTimeEntryField::StartedTime => &mut form.started_time,
+                    NewTimeEntryField::StoppedDate => &mut form.stopped_date,
+                    NewTimeEntryField::StoppedTime => &mut form.stopped_time,
+                    NewTimeEntryField::Duration => &mut form.duration,
+                };
+                *form_field = value;
+                form.error = None;
+                form.overlap_warning = None;
+            }
+        }
+        Msg::ToggleNewTimeEntryDuration(client_id, project_id) => {
+            let form = find_project_mut(&mut model.clients, client_id, project_id)
+                .and_then(|project| project.new_time_entry_form.as_mut());
+
+            if let Some(form) = form {
+                form.use_duration = not(form.use_duration);
+                form.error = None;
+                form.overlap_warning = None;
+            }
+        }
+        Msg::SaveNewTimeEntry(client_id, project_id) => {
+            use graphql::mutations::add_time_entry as mutation_mod;
+
+            let clients = match model.clients.loaded_mut() {
+                Some(clients) => clients,
+                None => return,
+            };
+            let form = match clients
+                .get(&client_id)
+                .and_then(|client| client.projects.get(&project_id))
+                .and_then(|project| project.new_time_entry_form.as_ref())
+            {
+                Some(form) => form,
+                None => return,
+            };
+            let time_entry_id = TimeEntryId::new();
+            let parsed = form.parse(preferences);
+            let overlap = match &parsed {
+                Ok((started, stopped)) => {
+                    check_overlaps(clients, time_entry_id, *started, Some(*stopped), preferences)
+                        .err()
+                }
+                Err(_) => None,
+            };
+            let overlap_confirmed = form.overlap_warning.is_some();
+            let name = form.name.clone();
+
+            let project = match clients
+                .get_mut(&client_id)
+                .and_then(|client| client.projects.get_mut(&project_id))
+            {
+                Some(project) => project,
+                None => return,
+            };
+            let (started, stopped) = match parsed {
+                Ok(interval) => interval,
+                Err(error) => {
+                    if let Some(form) = project.new_time_entry_form.as_mut() {
+                        form.error = Some(error);
+                    }
+                    return;
+                }
+            };
+            if let (Some(overlap), false) = (overlap, overlap_confirmed) {
+                if let Some(form) = project.new_time_entry_form.as_mut() {
+                    form.overlap_warning = Some(overlap);
+                }
+                return;
+            }
+
+            let input = graphql::types::AddTimeEntryInput {
+                id: time_entry_id.to_string(),
+                name: name.clone(),
+                started: graphql::types::DateTime(started.to_rfc3339()),
+                stopped: Some(graphql::types::DateTime(stopped.to_rfc3339())),
+                project: graphql::types::ProjectRef {
+                    id: Some(project_id.to_string()),
+                },
+            };
+            project.time_entries.insert(
+                time_entry_id,
+                TimeEntry {
+                    name,
+                    name_change: None,
+                    started,
+                    stopped: Some(stopped),
+                    change: None,
+                    change_error: None,
+                    split_form: None,
+                },
+            );
+            project.new_time_entry_form = None;
+            let change = sync_time_entries(store, &model.clients, vec![time_entry_id]);
+
+            let operation =
+                mutation_mod::Mutation::build(mutation_mod::Arguments { input: vec![input] });
+            model.changes.save(
+                graphql_config,
+                &mut orders.proxy(Msg::Changes),
+                operation,
+                change,
+            );
+        }
+
+        Msg::ApplyTimeEntryFix(client_id, project_id, time_entry_id, fix) => {
+            use graphql::mutations::update_time_entry as mutation_mod;
+
+            let time_entry = model.clients.loaded_mut().and_then(|clients| {
+                find_time_entry_mut(clients, client_id, project_id, time_entry_id)
+            });
+
+            if let Some(time_entry) = time_entry {
+                let patch = match fix {
+                    analysis::Fix::TrimStopped(stopped) => {
+                        time_entry.stopped = Some(stopped);
+                        graphql::types::TimeEntryPatch {
+                            stopped: Some(graphql::types::DateTime(stopped.to_rfc3339())),
+                            ..Default::default()
+                        }
+                    }
+                    analysis::Fix::DelayStarted(started) => {
+                        time_entry.started = started;
+                        graphql::types::TimeEntryPatch {
+                            started: Some(graphql::types::DateTime(started.to_rfc3339())),
+                            ..Default::default()
+                        }
+                    }
+                };
+                time_entry.change = None;
+                time_entry.change_error = None;
+                let change = sync_time_entries(store, &model.clients, vec![time_entry_id]);
+
+                let input = graphql::types::UpdateTimeEntryInput::new(
+                    graphql::types::TimeEntryFilter::id(time_entry_id),
+                    patch,
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    change,
+                );
+            }
+        }
+
+        Msg::OpenSplitForm(client_id, project_id, time_entry_id) => {
+            let time_entry = model.clients.loaded_mut().and_then(|clients| {
+                find_time_entry_mut(clients, client_id, project_id, time_entry_id)
+            });
+            if let Some(time_entry) = time_entry {
+                let middle = time_entry.started
+                    + (time_entry.stopped.unwrap_or_else(chrono::Local::now) - time_entry.started)
+                        / 2;
+                time_entry.split_form = Some(SplitForm {
+                    time: preferences.format_time(&middle),
+                    name: format!("{} (2)", time_entry.name),
+                    error: None,
+                });
+            }
+        }
+        Msg::CloseSplitForm(client_id, project_id, time_entry_id) => {
+            let time_entry = model.clients.loaded_mut().and_then(|clients| {
+                find_time_entry_mut(clients, client_id, project_id, time_entry_id)
+            });
+            if let Some(time_entry) = time_entry {
+                time_entry.split_form = None;
+            }
+        }
+        Msg::SplitTimeChanged(client_id, project_id, time_entry_id, time) => {
+            let split_form = model
+                .clients
+                .loaded_mut()
+                .and_then(|clients| {
+                    find_time_entry_mut(clients, client_id, project_id, time_entry_id)
+                })
+                .and_then(|time_entry| time_entry.split_form.as_mut());
+            if let Some(split_form) = split_form {
+                split_form.time = time;
+                split_form.error = None;
+            }
+        }
+        Msg::SplitNameChanged(client_id, project_id, time_entry_id, name) => {
+            let split_form = model
+                .clients
+                .loaded_mut()
+                .and_then(|clients| {
+                    find_time_entry_mut(clients, client_id, project_id, time_entry_id)
+                })
+                .and_then(|time_entry| time_entry.split_form.as_mut());
+            if let Some(split_form) = split_form {
+                split_form.name = name;
+            }
+        }
+        Msg::SplitTimeEntry(client_id, project_id, time_entry_id) => {
+            let project = match find_project_mut(&mut model.clients, client_id, project_id) {
+                Some(project) => project,
+                None => return,
+            };
+            let time_entry = match project.time_entries.get_mut(&time_entry_id) {
+                Some(time_entry) => time_entry,
+                None => return,
+            };
+            let split_form = match time_entry.split_form.as_mut() {
+                Some(split_form) => split_form,
+                None => return,
+            };
+            let split_at = match parse_split_time(
+                &split_form.time,
+                time_entry.started,
+                time_entry.stopped,
+                preferences,
+            ) {
+                Ok(split_at) => split_at,
+                Err(error) => {
+                    split_form.error = Some(error);
+                    return;
+                }
+            };
+
+            let new_parts = vec![TimeEntry::new(
+                split_form.name.clone(),
+                split_at,
+                time_entry.stopped,
+            )];
+            let (operation, change) =
+                split_time_entry(project, project_id, time_entry_id, split_at, new_parts, store);
+            model.changes.save(
+                graphql_config,
+                &mut orders.proxy(Msg::Changes),
+                operation,
+                change,
+            );
+        }
+        Msg::MergeWithPrevious(client_id, project_id, time_entry_id) => {
+            use graphql::mutations::merge_time_entries as mutation_mod;
+
+            let project = match find_project_mut(&mut model.clients, client_id, project_id) {
+                Some(project) => project,
+                None => return,
+            };
+            let stopped = project
+                .time_entries
+                .get(&time_entry_id)
+                .and_then(|time_entry| time_entry.stopped);
+            let (previous_id, stopped) = match (
+                previous_mergeable_time_entry(project, time_entry_id),
+                stopped,
+            ) {
+                (Some(previous_id), Some(stopped)) => (previous_id, stopped),
+                _ => return,
+            };
+
+            project.time_entries.remove(&time_entry_id);
+            if let Some(previous) = project.time_entries.get_mut(&previous_id) {
+                previous.stopped = Some(stopped);
+            }
+            let change = sync_time_entries(store, &model.clients, vec![time_entry_id, previous_id]);
+
+            let operation = mutation_mod::Mutation::build(mutation_mod::Arguments {
+                input: graphql::types::UpdateTimeEntryInput::new(
+                    graphql::types::TimeEntryFilter::id(previous_id),
+                    graphql::types::TimeEntryPatch {
+                        stopped: Some(graphql::types::DateTime(stopped.to_rfc3339())),
+                        ..Default::default()
+                    },
+                ),
+                time_entry: graphql::types::TimeEntryFilter::id(time_entry_id),
+            });
+            model.changes.save(
+                graphql_config,
+                &mut orders.proxy(Msg::Changes),
+                operation,
+                change,
+            );
+        }
+
+        Msg::ToggleTimeEntrySelection(time_entry_id) => {
+            if not(model.selected_time_entries.remove(&time_entry_id)) {
+                model.selected_time_entries.insert(time_entry_id);
+            }
+        }
+        Msg::ClearTimeEntrySelection => model.selected_time_entries.clear(),
+        Msg::MoveTimeEntries(time_entry_ids, target_client_id, target_project_id) => {
+            use graphql::mutations::update_time_entry as mutation_mod;
+
+            let clients = match model.clients.loaded_mut() {
+                Some(clients) => clients,
+                None => return,
+            };
+            if clients
+                .get(&target_client_id)
+                .and_then(|client| client.projects.get(&target_project_id))
+                .is_none()
+            {
+                return;
+            }
+
+            let mut moved_time_entries = Vec::new();
+            for (client_id, client) in clients.iter_mut() {
+                for (project_id, project) in client.projects.iter_mut() {
+                    if (*client_id, *project_id) == (target_client_id, target_project_id) {
+                        continue;
+                    }
+                    for time_entry_id in &time_entry_ids {
+                        if let Some(time_entry) = project.time_entries.remove(time_entry_id) {
+                            moved_time_entries.push((*time_entry_id, time_entry));
+                        }
+                    }
+                }
+            }
+            let moved_time_entries_filter = graphql::types::TimeEntryFilter::any_of(
+                moved_time_entries
+                    .iter()
+                    .map(|(time_entry_id, _)| time_entry_id.to_string()),
+            );
+            let moved_time_entries_filter = match moved_time_entries_filter {
+                Some(filter) => filter,
+                None => return,
+            };
+            for (time_entry_id, _) in &moved_time_entries {
+                model.selected_time_entries.remove(time_entry_id);
+            }
+            let moved_time_entry_ids = moved_time_entries
+                .iter()
+                .map(|(time_entry_id, _)| *time_entry_id)
+                .collect();
+            if let Some(target_project) = clients
+                .get_mut(&target_client_id)
+                .and_then(|client| client.projects.get_mut(&target_project_id))
+            {
+                target_project.time_entries.extend(moved_time_entries);
+            }
+            let change = sync_time_entries(store, &model.clients, moved_time_entry_ids);
+
+            let input = graphql::types::UpdateTimeEntryInput::new(
+                moved_time_entries_filter,
+                graphql::types::TimeEntryPatch {
+                    project: Some(graphql::types::ProjectRef {
+                        id: Some(target_project_id.to_string()),
+                    }),
+                    ..Default::default()
+                },
+            );
+            let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+            model.changes.save(
+                graphql_config,
+                &mut orders.proxy(Msg::Changes),
+                operation,
+                change,
+            );
+        }
+
+        Msg::UserActivity => {
+            let now = chrono::Local::now();
+            let idle_started = model
+                .idle_since
+                .take()
+                .or_else(|| detect_idle_start(model, now, preferences));
+            model.last_activity = now;
+
+            match idle_started {
+                Some(idle_started) if model.idle_prompt.is_none() => {
+                    model.idle_prompt = Some(IdlePrompt {
+                        idle_started,
+                        returned: now,
+                    });
+                }
+                // Activity events are frequent, don't re-render for each of them.
+                _ => orders.skip(),
+            }
+        }
+        Msg::KeepIdleTime => model.idle_prompt = None,
+        Msg::DiscardIdleTime => {
+            use graphql::mutations::update_time_entry as mutation_mod;
+
+            let idle_started = match model.idle_prompt.take() {
+                Some(idle_prompt) => idle_prompt.idle_started,
+                None => return,
+            };
+            let clients = match model.clients.loaded_mut() {
+                Some(clients) => clients,
+                None => return,
+            };
+            for (client_id, project_id, time_entry_id) in idle_time_entries(clients, idle_started) {
+                if let Some(time_entry) =
+                    find_time_entry_mut(clients, client_id, project_id, time_entry_id)
+                {
+                    time_entry.stopped = Some(idle_started);
+                }
+                let before = Snapshot::default().time_entries(store, Some(time_entry_id));
+                if let Some(stored_time_entry) = store.time_entries.get_mut(&time_entry_id) {
+                    stored_time_entry.stopped = Some(idle_started);
+                }
+                let input = graphql::types::UpdateTimeEntryInput::new(
+                    graphql::types::TimeEntryFilter::id(time_entry_id),
+                    graphql::types::TimeEntryPatch {
+                        stopped: Some(graphql::types::DateTime(idle_started.to_rfc3339())),
+                        ..Default::default()
+                    },
+                );
+                let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    store.change_since(before),
+                );
+            }
+        }
+        Msg::SplitIdleTime => {
+            let IdlePrompt {
+                idle_started,
+                returned,
+            } = match model.idle_prompt.take() {
+                Some(idle_prompt) => idle_prompt,
+                None => return,
+            };
+            let time_entry_ids = match model.clients.loaded() {
+                Some(clients) => idle_time_entries(clients, idle_started),
+                None => return,
+            };
+            for (client_id, project_id, time_entry_id) in time_entry_ids {
+                let project = match find_project_mut(&mut model.clients, client_id, project_id) {
+                    Some(project) => project,
+                    None => continue,
+                };
+                let time_entry = match project.time_entries.get(&time_entry_id) {
+                    Some(time_entry) => time_entry,
+                    None => continue,
+                };
+                // The idle time gets its own entry and the timer keeps running from the return.
+                let new_parts = vec![
+                    TimeEntry::new(
+                        format!("{} (idle)", time_entry.name),
+                        idle_started,
+                        Some(returned),
+                    ),
+                    TimeEntry::new(time_entry.name.clone(), returned, None),
+                ];
+                let (operation, change) = split_time_entry(
+                    project,
+                    project_id,
+                    time_entry_id,
+                    idle_started,
+                    new_parts,
+                    store,
+                );
+                model.changes.save(
+                    graphql_config,
+                    &mut orders.proxy(Msg::Changes),
+                    operation,
+                    change,
+                );
+            }
+        }
+
+        Msg::StartFocusSession(client_id, project_id) => {
+            if let Some(previous) = focus_session.take() {
+                if previous.phase == FocusPhase::Work && previous.project_id != project_id {
+                    orders.send_msg(Msg::Stop(previous.client_id, previous.project_id));
+                }
+            }
+            let now = chrono::Local::now();
+            *focus_session = Some(FocusSession::new(
+                client_id,
+                project_id,
+                now,
+                &preferences.pomodoro,
+            ));
+            // The first work interval doesn't continue a running time entry.
+            let running = model
+                .clients
+                .loaded()
+                .and_then(|clients| clients.get(&client_id))
+                .and_then(|client| client.projects.get(&project_id))
+                .map(|project| {
+                    project
+                        .time_entries
+                        .values()
+                        .any(|time_entry| time_entry.stopped.is_none())
+                });
+            if running == Some(true) {
+                orders.send_msg(Msg::Stop(client_id, project_id));
+            }
+            orders
+                .send_msg(Msg::Start(client_id, project_id))
+                .perform_cmd(notification::request_permission());
+        }
+        Msg::StopFocusSession => {
+            if let Some(focus_session) = focus_session.take() {
+                if focus_session.phase == FocusPhase::Work {
+                    orders.send_msg(Msg::Stop(focus_session.client_id, focus_session.project_id));
+                }
+            }
+        }
+
+        Msg::ContinueTimeEntry(client_id, project_id, name) => {
+            log!("Msg::ContinueTimeEntry", client_id, project_id);
+            let single_active_timer = preferences.single_active_timer;
+            let save = timer::start(store, graphql_config, project_id, name, single_active_timer);
+            save_timer(model, save, store, orders);
+        }
+        Msg::ToggleFavourite(project_id, name) => {
+            let favourite = (project_id, name);
+            if not(model.favourites.remove(&favourite)) {
+                model.favourites.insert(favourite);
+            }
+            save_favourites(&model.favourites);
+        }
+
+        Msg::OnSecondTick => {
+            let now = chrono::Local::now();
+            if model.idle_since.is_none() {
+                model.idle_since = detect_idle_start(model, now, preferences);
+            }
+            // Running time entries can become too long without any change.
+            let derived_data_outdated = model
+                .derived_data
+                .computed
+                .map_or(true, |computed| now - computed >= chrono::Duration::minutes(1));
+            if derived_data_outdated {
+                model.derived_data = DerivedData::new(&model.clients, preferences);
+            }
+
+            // The app ticks the focus session and shows it in the title.
+            if focus_session.is_some() {
+                return;
+            }
+
+            let active_time_entry = model
+                .clients
+                .loaded()
+                .and_then(latest_active_time_entry);
+
+            if let Some((project, time_entry)) = active_time_entry {
+                let elapsed = chrono::Local::now() - time_entry.started;
+                document().set_title(&format!(
+                    "{} {} | {}",
+                    preferences.format_duration(&elapsed),
+                    project.name,
+                    TITLE
+                ));
+            } else {
+                document().set_title(TITLE);
+                // Nothing is ticking, there is nothing to re-render.
+                orders.skip();
+            }
+        }
+    }
+}
+
+/// Shows the time entries started or stopped by `timer` and saves them.
+fn save_timer(
+    model: &mut Model,
+    save: timer::Save,
+    store: &Store,
+    orders: &mut impl Orders<Msg>,
+) {
+    let timer::Save { change, request } = save;
+    restore_time_entries(store, &mut model.clients, change.time_entry_ids().collect());
+    model.changes.save_request(&mut orders.proxy(Msg::Changes), request, change);
+}
+
+/// Writes the saved state of the time entries to the store and returns the change.
+///
+/// Time entries that are no longer in `clients` are removed from the store.
+fn sync_time_entries(
+    store: &mut Store,
+    clients: &RemoteData<BTreeMap<ClientId, Client>>,
+    time_entry_ids: Vec<TimeEntryId>,
+) -> store::Change {
+    let clients = match clients.loaded() {
+        Some(clients) => clients,
+        None => return store::Change::default(),
+    };
+    let before = Snapshot::default().time_entries(store, time_entry_ids.iter().copied());
+    for time_entry_id in time_entry_ids {
+        let time_entry = clients
+            .values()
+            .flat_map(|client| client.projects.iter())
+            .find_map(|(project_id, project)| {
+                Some((*project_id, project.time_entries.get(&time_entry_id)?))
+            });
+        match time_entry {
+            Some((project_id, time_entry)) => {
+                store
+                    .time_entries
+                    .insert(time_entry_id, time_entry.to_stored(project_id));
+            }
+            None => {
+                store.time_entries.remove(&time_entry_id);
+            }
+        }
+    }
+    store.change_since(before)
+}
+
+/// Reads the time entries back from the store, e.g. after a failed change was reverted.
+///
+/// Time entries that are no longer in the store are removed from `clients`.
+/// The other ones keep their unsaved changes and open forms.
+fn restore_time_entries(
+    store: &Store,
+    clients: &mut RemoteData<BTreeMap<ClientId, Client>>,
+    time_entry_ids: Vec<TimeEntryId>,
+) {
+    let clients = match clients.loaded_mut() {
+        Some(clients) => clients,
+        None => return,
+    };
+    for time_entry_id in time_entry_ids {
+        let time_entry = clients
+            .values_mut()
+            .flat_map(|client| client.projects.values_mut())
+            .find_map(|project| project.time_entries.remove(&time_entry_id));
+        let stored_time_entry = match store.time_entries.get(&time_entry_id) {
+            Some(stored_time_entry) => stored_time_entry,
+            None => continue,
+        };
+        let project = store
+            .projects
+            .get(&stored_time_entry.project_id)
+            .and_then(|project| clients.get_mut(&project.client_id))
+            .and_then(|client| client.projects.get_mut(&stored_time_entry.project_id));
+        let project = match project {
+            Some(project) => project,
+            None => continue,
+        };
+        let time_entry = match time_entry {
+            Some(mut time_entry) => {
+                time_entry.name = stored_time_entry.name.clone();
+                time_entry.started = stored_time_entry.started;
+                time_entry.stopped = stored_time_entry.stopped;
+                time_entry
+            }
+            None => TimeEntry::new(
+                stored_time_entry.name.clone(),
+                stored_time_entry.started,
+                stored_time_entry.stopped,
+            ),
+        };
+        project.time_entries.insert(time_entry_id, time_entry);
+    }
+}
+
+/// Time tracked by each client this week, entries crossing the week start count partially.
+fn tracked_this_week(
+    clients: &BTreeMap<ClientId, Client>,
+    now: DateTime<Local>,
+    preferences: &Preferences,
+) -> BTreeMap<ClientId, chrono::Duration> {
+    let week_start = match preferences.start_of_week(&now) {
+        Ok(week_start) => week_start,
+        Err(_) => return BTreeMap::new(),
+    };
+    clients
+        .iter()
+        .map(|(client_id, client)| {
+            let tracked = client
+                .projects
+                .values()
+                .flat_map(|project| project.time_entries.values())
+                .map(|time_entry| {
+                    let started = time_entry.started.max(week_start);
+                    let stopped = time_entry.stopped.unwrap_or(now);
+                    (stopped - started).max(chrono::Duration::zero())
+                })
+                .fold(chrono::Duration::zero(), |total, duration| total + duration);
+            (*client_id, tracked)
+        })
+        .collect()
+}
+
+/// The last distinct (project, name) pairs, the most recent first.
+fn recent_time_entries(clients: &BTreeMap<ClientId, Client>) -> Vec<(ClientId, ProjectId, String)> {
+    let mut time_entries = clients
+        .iter()
+        .flat_map(|(client_id, client)| {
+            client.projects.iter().flat_map(move |(project_id, project)| {
+                project.time_entries.values().map(move |time_entry| {
+                    let name = time_entry.name.as_str();
+                    (time_entry.started, *client_id, *project_id, name)
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+    time_entries.sort_by_key(|(started, ..)| std::cmp::Reverse(*started));
+
+    let mut seen = BTreeSet::new();
+    time_entries
+        .into_iter()
+        .filter(|(_, _, project_id, name)| seen.insert((*project_id, *name)))
+        .take(RECENT_TIME_ENTRIES)
+        .map(|(_, client_id, project_id, name)| (client_id, project_id, name.to_owned()))
+        .collect()
+}
+
+/// Returns the last activity if the user has been idle for too long while a timer was running.
+fn detect_idle_start(
+    model: &Model,
+    now: DateTime<Local>,
+    preferences: &Preferences,
+) -> Option<DateTime<Local>> {
+    let threshold = preferences.idle_threshold()?;
+    if now - model.last_activity < threshold {
+        return None;
+    }
+    let clients = model.clients.loaded()?;
+    Some(model.last_activity)
+        .filter(|idle_started| not(idle_time_entries(clients, *idle_started).is_empty()))
+}
+
+/// Running time entries that were already running when the user went idle.
+fn idle_time_entries(
+    clients: &BTreeMap<ClientId, Client>,
+    idle_started: DateTime<Local>,
+) -> Vec<(ClientId, ProjectId, TimeEntryId)> {
+    clients
+        .iter()
+        .flat_map(|(client_id, client)| {
+            client.projects.iter().flat_map(move |(project_id, project)| {
+                project
+                    .time_entries
+                    .iter()
+                    .filter(|(_, time_entry)| {
+                        time_entry.stopped.is_none() && time_entry.started < idle_started
+                    })
+                    .map(move |(time_entry_id, _)| (*client_id, *project_id, *time_entry_id))
+            })
+        })
+        .collect()
+}
+
+/// Stops the time entry at `split_at` and adds `new_parts` in one batch of mutations.
+///
+/// The time entry keeps its id so nothing referring to it is lost.
+/// Returns the mutation together with the change made to the store.
+fn split_time_entry(
+    project: &mut Project,
+    project_id: ProjectId,
+    time_entry_id: TimeEntryId,
+    split_at: DateTime<Local>,
+    new_parts: Vec<TimeEntry>,
+    store: &mut Store,
+) -> (
+    cynic::Operation<'static, graphql::mutations::stop_and_add_time_entry::Mutation>,
+    store::Change,
+) {
+    use graphql::mutations::stop_and_add_time_entry as mutation_mod;
+
+    let new_parts = new_parts
+        .into_iter()
+        .map(|time_entry| (TimeEntryId::new(), time_entry))
+        .collect::<Vec<_>>();
+    let input = new_parts
+        .iter()
+        .map(|(id, time_entry)| graphql::types::AddTimeEntryInput {
+            id: id.to_string(),
+            name: time_entry.name.clone(),
+            started: graphql::types::DateTime(time_entry.started.to_rfc3339()),
+            stopped: time_entry
+                .stopped
+                .map(|stopped| graphql::types::DateTime(stopped.to_rfc3339())),
+            project: graphql::types::ProjectRef {
+                id: Some(project_id.to_string()),
+            },
+        })
+        .collect();
+
+    let before = Snapshot::default().time_entries(
+        store,
+        iter::once(time_entry_id).chain(new_parts.iter().map(|(id, _)| *id)),
+    );
+    if let Some(time_entry) = store.time_entries.get_mut(&time_entry_id) {
+        time_entry.stopped = Some(split_at);
+    }
+    for (id, time_entry) in &new_parts {
+        store.time_entries.insert(*id, time_entry.to_stored(project_id));
+    }
+    if let Some(time_entry) = project.time_entries.get_mut(&time_entry_id) {
+        time_entry.stopped = Some(split_at);
+        time_entry.split_form = None;
+    }
+    project.time_entries.extend(new_parts);
+    let operation = mutation_mod::Mutation::build(mutation_mod::Arguments {
+        stop: graphql::types::UpdateTimeEntryInput::new(
+            graphql::types::TimeEntryFilter::id(time_entry_id),
+            graphql::types::TimeEntryPatch {
+                stopped: Some(graphql::types::DateTime(split_at.to_rfc3339())),
+                ..Default::default()
+            },
+        ),
+        input,
+    });
+    (operation, store.change_since(before))
+}
+
+fn latest_active_time_entry(
+    clients: &BTreeMap<ClientId, Client>,
+) -> Option<(&Project, &TimeEntry)> {
+    clients
+        .values()
+        .flat_map(|client| client.projects.values())
+        .flat_map(|project| {
+            project
+                .time_entries
+                .values()
+                .filter(|time_entry| time_entry.stopped.is_none())
+                .map(move |time_entry| (project, time_entry))
+        })
+        .max_by_key(|(_, time_entry)| time_entry.started)
+}
+
+/// Overlaps, non-positive durations and suspiciously long entries of all the user's entries.
+fn time_entry_warnings(
+    clients: &BTreeMap<ClientId, Client>,
+    preferences: &Preferences,
+) -> TimeEntryWarnings {
+    let time_entries = || {
+        clients
+            .values()
+            .flat_map(|client| client.projects.values())
+            .flat_map(|project| project.time_entries.iter())
+    };
+    let intervals = time_entries().map(|(time_entry_id, time_entry)| analysis::Interval {
+        id: *time_entry_id,
+        started: time_entry.started,
+        stopped: time_entry.stopped,
+    });
+    let names = time_entries()
+        .map(|(time_entry_id, time_entry)| (*time_entry_id, time_entry.name.as_str()))
+        .collect::<BTreeMap<_, _>>();
+
+    analysis::analyze(intervals, chrono::Local::now())
+        .into_iter()
+        .map(|(time_entry_id, warnings)| {
+            let warnings = warnings
+                .into_iter()
+                .map(|warning| {
+                    let message = match warning.issue {
+                        analysis::Issue::Overlap { other, overlap } => format!(
+                            "Overlaps \"{}\" by {}.",
+                            names.get(&other).copied().unwrap_or_default(),
+                            preferences.format_duration(&overlap)
+                        ),
+                        analysis::Issue::NonPositiveDuration => {
+                            "Stops before it starts or has no duration.".to_owned()
+                        }
+                        analysis::Issue::TooLong { duration } => format!(
+                            "Lasts {}, longer than {} hours.",
+                            preferences.format_duration(&duration),
+                            analysis::MAX_USUAL_DURATION_HOURS
+                        ),
+                    };
+                    (message, warning.fix)
+                })
+                .collect();
+            (time_entry_id, warnings)
+        })
+        .collect()
+}
+
+/// The split time is on the start date or, for entries over midnight, on the stop date.
+fn parse_split_time(
+    time: &str,
+    started: DateTime<Local>,
+    stopped: Option<DateTime<Local>>,
+    preferences: &Preferences,
+) -> Result<DateTime<Local>, String> {
+    let time = preferences.parse_time(time)?;
+    let stopped = stopped.unwrap_or_else(chrono::Local::now);
+
+    let candidates = vec![
+        preferences.with_time(&started, time),
+        preferences.with_time(&stopped, time),
+    ];
+    candidates
+        .into_iter()
+        .filter_map(Result::ok)
+        .find(|split_at| started < *split_at && *split_at < stopped)
+        .ok_or_else(|| "The split time has to be between the start and the stop.".to_owned())
+}
+
+/// The stopped entry right before `time_entry_id` with the same name.
+fn previous_mergeable_time_entry(
+    project: &Project,
+    time_entry_id: TimeEntryId,
+) -> Option<TimeEntryId> {
+    let time_entry = project.time_entries.get(&time_entry_id)?;
+    let (previous_id, previous) = project
+        .time_entries
+        .iter()
+        .filter(|(_, other)| other.started < time_entry.started)
+        .max_by_key(|(_, other)| other.started)?;
+
+    let gap = time_entry.started - previous.stopped?;
+    let contiguous = gap >= chrono::Duration::zero()
+        && gap <= chrono::Duration::seconds(MAX_MERGE_GAP_SECONDS);
+    let same_name = previous.name.trim() == time_entry.name.trim();
+
+    Some(*previous_id).filter(|_| contiguous && same_name)
+}
+
+fn find_project_mut(
+    clients: &mut RemoteData<BTreeMap<ClientId, Client>>,
+    client_id: ClientId,
+    project_id: ProjectId,
+) -> Option<&mut Project> {
+    clients
+        .loaded_mut()?
+        .get_mut(&client_id)?
+        .projects
+        .get_mut(&project_id)
+}
+
+fn find_time_entry(
+    clients: &BTreeMap<ClientId, Client>,
+    client_id: ClientId,
+    project_id: ProjectId,
+    time_entry_id: TimeEntryId,
+) -> Option<&TimeEntry> {
+    clients
+        .get(&client_id)?
+        .projects
+        .get(&project_id)?
+        .time_entries
+        .get(&time_entry_id)
+}
+
+fn find_time_entry_mut(
+    clients: &mut BTreeMap<ClientId, Client>,
+    client_id: ClientId,
+    project_id: ProjectId,
+    time_entry_id: TimeEntryId,
+) -> Option<&mut TimeEntry> {
+    clients
+        .get_mut(&client_id)?
+        .projects
+        .get_mut(&project_id)?
+        .time_entries
+        .get_mut(&time_entry_id)
+}
+
+/// Returns the new `started` and `stopped`.
+///
+/// Editing `started` keeps `stopped`, or the duration with `Preferences::keep_duration`.
+/// Editing `Duration` moves `stopped`.
+fn apply_time_entry_change(
+    change: &TimeEntryChange,
+    time_entry: &TimeEntry,
+    preferences: &Preferences,
+) -> Result<(DateTime<Local>, Option<DateTime<Local>>), String> {
+    let started = time_entry.started;
+    let stopped = time_entry.stopped;
+    let stopped_or_error = || stopped.ok_or_else(|| "The time entry is still running.".to_owned());
+    let shifted_stopped = |new_started: DateTime<Local>| {
+        stopped
+            .filter(|_| preferences.keep_duration)
+            .map_or(stopped, |stopped| Some(stopped + (new_started - started)))
+    };
+
+    let (started, stopped) = match change {
+        TimeEntryChange::StartedDate(date) => {
+            let new_started = preferences.with_date(&started, preferences.parse_date(date)?)?;
+            (new_started, shifted_stopped(new_started))
+        }
+        TimeEntryChange::StartedTime(time) => {
+            let new_started = preferences.with_time(&started, preferences.parse_time(time)?)?;
+            (new_started, shifted_stopped(new_started))
+        }
+        TimeEntryChange::StoppedDate(date) => {
+            let stopped =
+                preferences.with_date(&stopped_or_error()?, preferences.parse_date(date)?)?;
+            (started, Some(stopped))
         }
+        TimeEntryChange::StoppedTime(time) => {
+            let stopped =
+                preferences.with_time(&stopped_or_error()?, preferences.parse_time(time)?)?;
+            (started, Some(stopped))
+        }
+        TimeEntryChange::Duration(duration) => {
+            stopped_or_error()?;
+            let duration = duration::parse(duration).map_err(|error| error.to_string())?;
+            (started, Some(started + duration))
+        }
+    };
+
+    match stopped {
+        Some(stopped) if stopped < started => {
+            Err("The time entry would stop before it started.".to_owned())
+        }
+        None if started > Local::now() => {
+            Err("The running time entry can't start in the future.".to_owned())
+        }
+        _ => Ok((started, stopped)),
+    }
+}
 
-        Msg::OnSecondTick => {}
+/// Running time entries end now.
+fn check_overlaps(
+    clients: &BTreeMap<ClientId, Client>,
+    time_entry_id: TimeEntryId,
+    started: DateTime<Local>,
+    stopped: Option<DateTime<Local>>,
+    preferences: &Preferences,
+) -> Result<(), String> {
+    let now = Local::now();
+    let stopped = stopped.unwrap_or(now);
+
+    let other_time_entries = clients
+        .values()
+        .flat_map(|client| client.projects.values())
+        .flat_map(|project| project.time_entries.iter())
+        .filter(|(other_id, _)| **other_id != time_entry_id)
+        .map(|(_, other)| other);
+
+    for other in other_time_entries {
+        let other_stopped = other.stopped.unwrap_or(now);
+        if started < other_stopped && other.started < stopped {
+            return Err(format!(
+                "The time entry would overlap \"{}\" ({} {} - {} {}).",
+                other.name,
+                preferences.format_date(&other.started),
+                preferences.format_time(&other.started),
+                preferences.format_date(&other_stopped),
+                preferences.format_time(&other_stopped),
+            ));
+        }
     }
+    Ok(())
 }
 
 // ------ ------
 //     View
 // ------ ------
 
-pub fn view(model: &Model) -> Node<Msg> {
+pub fn view(
+    model: &Model,
+    focus_session: Option<&FocusSession>,
+    preferences: &Preferences,
+) -> Node<Msg> {
     section![
-        h1![C!["title", "ml-6", "mt-6", "mb-5"], "Time Tracker",],
+        h1![
+            C!["title", "ml-6", "mt-6", "mb-5"],
+            "Time Tracker",
+            changes::view_status(&model.changes.status, preferences),
+        ],
+        model
+            .idle_prompt
+            .as_ref()
+            .map(|idle_prompt| view_idle_prompt(idle_prompt, preferences)),
+        focus_session.map(|focus_session| {
+            view_focus_session(focus_session, model.clients.loaded(), preferences)
+        }),
         div![
             C!["columns", "is-centered"],
             div![
                 C!["column", "is-two-thirds"],
-                match &model.clients {
-                    RemoteData::NotAsked | RemoteData::Loading => {
-                        progress![C!["progress", "is-link", "mt-6"]].into_nodes()
-                    }
-                    RemoteData::Loaded(clients) => {
-                        clients
-                            .iter()
-                            .rev()
-                            .map(|(client_id, client)| view_client(*client_id, client))
-                            .collect()
-                    }
-                }
+                remote_data::view(&model.clients, || Msg::RetryStoreFetch, |clients| {
+                    let derived_data = &model.derived_data;
+                    let mut nodes = vec![
+                        view_quick_start(
+                            clients,
+                            &derived_data.recent_time_entries,
+                            &model.favourites,
+                        ),
+                        view_selection_bar(
+                            &model.selected_time_entries,
+                            &derived_data.project_options,
+                        ),
+                    ];
+                    nodes.extend(clients.iter().rev().map(|(client_id, client)| {
+                        view_client(
+                            *client_id,
+                            client,
+                            derived_data.tracked_this_week.get(client_id).copied(),
+                            &derived_data.warnings,
+                            &model.selected_time_entries,
+                            &derived_data.project_options,
+                            preferences,
+                        )
+                    }));
+                    nodes
+                })
             ]
         ]
     ]
 }
 
-fn view_client(client_id: ClientId, client: &Client) -> Node<Msg> {
+fn view_idle_prompt(idle_prompt: &IdlePrompt, preferences: &Preferences) -> Node<Msg> {
+    let idle_duration = idle_prompt.returned - idle_prompt.idle_started;
+    div![
+        C!["notification", "is-warning", "mx-6"],
+        p![format!(
+            "You have been idle since {} ({}). What should happen with the idle time of the running timers?",
+            preferences.format_time(&idle_prompt.idle_started),
+            preferences.format_duration(&idle_duration),
+        )],
+        div![
+            C!["buttons", "mt-3"],
+            button![
+                C!["button", "is-small", "is-rounded"],
+                ev(Ev::Click, |_| Msg::KeepIdleTime),
+                "Keep",
+            ],
+            button![
+                C!["button", "is-small", "is-rounded", "is-danger"],
+                ev(Ev::Click, |_| Msg::DiscardIdleTime),
+                "Discard",
+            ],
+            button![
+                C!["button", "is-small", "is-rounded", "is-link"],
+                ev(Ev::Click, |_| Msg::SplitIdleTime),
+                "Split into a separate entry",
+            ],
+        ],
+    ]
+}
+
+fn view_focus_session(
+    focus_session: &FocusSession,
+    clients: Option<&BTreeMap<ClientId, Client>>,
+    preferences: &Preferences,
+) -> Node<Msg> {
+    let project_name = clients
+        .and_then(|clients| clients.get(&focus_session.client_id))
+        .and_then(|client| client.projects.get(&focus_session.project_id))
+        .map(|project| project.name.as_str())
+        .unwrap_or_default();
+    let remaining = focus_session.phase_ends - chrono::Local::now();
+    div![
+        C![
+            "notification",
+            "mx-6",
+            if focus_session.phase == FocusPhase::Work {
+                "is-link"
+            } else {
+                "is-primary"
+            }
+        ],
+        div![
+            C!["level", "is-mobile"],
+            div![
+                C!["level-left"],
+                div![
+                    C!["level-item"],
+                    div![
+                        p![C!["is-size-4"], format!(
+                            "{} {}",
+                            focus_session.phase.label(),
+                            preferences.format_duration(&remaining.max(chrono::Duration::zero()))
+                        )],
+                        p![format!(
+                            "Focus on {}, {} work intervals completed",
+                            project_name, focus_session.completed_work_intervals
+                        )],
+                    ],
+                ],
+            ],
+            div![
+                C!["level-right"],
+                button![
+                    C!["button", "is-small", "is-rounded"],
+                    ev(Ev::Click, |_| Msg::StopFocusSession),
+                    "Stop focus",
+                ],
+            ],
+        ],
+    ]
+}
+
+fn view_quick_start(
+    clients: &BTreeMap<ClientId, Client>,
+    recent_time_entries: &[(ClientId, ProjectId, String)],
+    favourites: &BTreeSet<(ProjectId, String)>,
+) -> Node<Msg> {
+    // Favourites of deleted projects are hidden.
+    let favourite_rows = favourites.iter().filter_map(|(project_id, name)| {
+        clients.iter().find_map(|(client_id, client)| {
+            let project = client.projects.get(project_id)?;
+            Some((*client_id, *project_id, project, name.as_str()))
+        })
+    });
+    let recent_rows = recent_time_entries
+        .iter()
+        .filter(|(_, project_id, name)| not(favourites.contains(&(*project_id, name.clone()))))
+        .filter_map(|(client_id, project_id, name)| {
+            let project = clients.get(client_id)?.projects.get(project_id)?;
+            Some((*client_id, *project_id, project, name.as_str()))
+        });
+
+    let rows = favourite_rows
+        .map(|row| (row, true))
+        .chain(recent_rows.map(|row| (row, false)))
+        .map(|((client_id, project_id, project, name), favourite)| {
+            let client_name = clients
+                .get(&client_id)
+                .map(|client| client.name.as_str())
+                .unwrap_or_default();
+            let project_label = format!("{} / {}", client_name, project.name);
+            view_quick_start_row(client_id, project_id, project_label, name, favourite)
+        })
+        .collect::<Vec<_>>();
+
+    if rows.is_empty() {
+        return empty![];
+    }
+    div![
+        C!["box", "mt-6"],
+        h2![C!["subtitle", "mb-3"], "Recent"],
+        rows,
+    ]
+}
+
+fn view_quick_start_row(
+    client_id: ClientId,
+    project_id: ProjectId,
+    project_label: String,
+    name: &str,
+    favourite: bool,
+) -> Node<Msg> {
+    let toggle_name = name.to_owned();
+    let continue_name = name.to_owned();
+    div![
+        C!["level", "is-mobile", "mb-2"],
+        div![
+            C!["level-left"],
+            button![
+                C!["button", "is-small", "is-white", "level-item"],
+                attrs! {At::Title => if favourite { "Unpin" } else { "Pin" }},
+                ev(Ev::Click, move |_| Msg::ToggleFavourite(project_id, toggle_name)),
+                span![
+                    C!["icon", IF!(favourite => "has-text-warning")],
+                    i![C![if favourite { "fas" } else { "far" }, "fa-star"]],
+                ],
+            ],
+            div![
+                C!["level-item"],
+                div![
+                    p![if name.is_empty() { "Untitled" } else { name }],
+                    p![C!["is-size-7", "has-text-grey"], project_label],
+                ],
+            ],
+        ],
+        div![
+            C!["level-right"],
+            button![
+                C!["button", "is-small", "is-rounded", "is-primary", "level-item"],
+                ev(Ev::Click, move |_| Msg::ContinueTimeEntry(
+                    client_id,
+                    project_id,
+                    continue_name
+                )),
+                span![C!["icon"], i![C!["fas", "fa-play"]]],
+                span!["Continue"],
+            ],
+        ],
+    ]
+}
+
+fn project_options(clients: &BTreeMap<ClientId, Client>) -> ProjectOptions {
+    clients
+        .iter()
+        .flat_map(|(client_id, client)| {
+            client.projects.iter().map(move |(project_id, project)| {
+                (
+                    *client_id,
+                    *project_id,
+                    format!("{} / {}", client.name, project.name),
+                )
+            })
+        })
+        .collect()
+}
+
+fn view_selection_bar(
+    selected_time_entries: &BTreeSet<TimeEntryId>,
+    project_options: &ProjectOptions,
+) -> Node<Msg> {
+    if selected_time_entries.is_empty() {
+        return empty![];
+    }
+    let time_entry_ids = selected_time_entries.iter().copied().collect::<Vec<_>>();
+    div![
+        C!["box", "mt-6"],
+        div![
+            C!["level", "is-mobile"],
+            div![
+                C!["level-left"],
+                div![
+                    C!["level-item"],
+                    format!("{} selected", time_entry_ids.len()),
+                ],
+                div![
+                    C!["level-item"],
+                    view_project_picker(project_options.iter(), move |client_id, project_id| {
+                        Msg::MoveTimeEntries(time_entry_ids, client_id, project_id)
+                    }),
+                ],
+            ],
+            div![
+                C!["level-right"],
+                button![
+                    C!["button", "is-small", "is-rounded"],
+                    ev(Ev::Click, |_| Msg::ClearTimeEntrySelection),
+                    "Clear selection",
+                ],
+            ],
+        ],
+    ]
+}
+
+fn view_project_picker<'a>(
+    options: impl Iterator<Item = &'a (ClientId, ProjectId, String)>,
+    on_change: impl FnOnce(ClientId, ProjectId) -> Msg + Clone + 'static,
+) -> Node<Msg> {
+    let (targets, labels): (Vec<_>, Vec<_>) = options
+        .map(|(client_id, project_id, label)| ((*client_id, *project_id), label))
+        .unzip();
+    div![
+        C!["select", "is-small", "is-rounded"],
+        select![
+            option![
+                attrs! {At::Value => "", At::Selected => AtValue::None},
+                "Move to…",
+            ],
+            labels
+                .into_iter()
+                .enumerate()
+                .map(|(index, label)| option![attrs! {At::Value => index}, label]),
+            input_ev(Ev::Change, move |index| {
+                index
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| targets.get(index).copied())
+                    .map(|(client_id, project_id)| on_change(client_id, project_id))
+            }),
+        ],
+    ]
+}
+
+pub fn view_errors(model: &Model) -> Node<Msg> {
+    changes::view_errors(&model.changes).map_msg(Msg::Changes)
+}
+
+fn view_client(
+    client_id: ClientId,
+    client: &Client,
+    tracked_this_week: Option<chrono::Duration>,
+    warnings: &TimeEntryWarnings,
+    selected_time_entries: &BTreeSet<TimeEntryId>,
+    project_options: &ProjectOptions,
+    preferences: &Preferences,
+) -> Node<Msg> {
     div![
         C!["box", "has-background-link", "mt-6",],
         div![
             C!["level", "is-mobile"],
             div![C!["is-size-3", "has-text-link-light"], &client.name,],
+            tracked_this_week.map(|tracked| {
+                span![
+                    C!["tag", "is-link", "is-light"],
+                    format!("This week {}", preferences.format_duration(&tracked)),
+                ]
+            }),
         ],
         client
             .projects
             .iter()
             .rev()
-            .map(|(project_id, project)| view_project(client_id, *project_id, project)),
+            .map(|(project_id, project)| {
+                view_project(
+                    client_id,
+                    *project_id,
+                    project,
+                    warnings,
+                    selected_time_entries,
+                    project_options,
+                    preferences,
+                )
+            }),
     ]
 }
 
-fn view_project(client_id: ClientId, project_id: ProjectId, project: &Project) -> Node<Msg> {
+fn view_project(
+    client_id: ClientId,
+    project_id: ProjectId,
+    project: &Project,
+    warnings: &TimeEntryWarnings,
+    selected_time_entries: &BTreeSet<TimeEntryId>,
+    project_options: &ProjectOptions,
+    preferences: &Preferences,
+) -> Node<Msg> {
     let active_time_entry = project
         .time_entries
         .iter()
@@ -464,18 +2127,171 @@ fn view_project(client_id: ClientId, project_id: ProjectId, project: &Project) -
         div![
             C!["level", "is-mobile"],
             div![C!["is-size-4"], &project.name,],
-            view_start_stop_button(client_id, project_id, active_time_entry.is_some()),
+            div![
+                C!["buttons"],
+                view_focus_button(client_id, project_id),
+                view_start_stop_button(client_id, project_id, active_time_entry.is_some()),
+            ],
         ],
+        match &project.new_time_entry_form {
+            Some(form) => view_new_time_entry_form(client_id, project_id, form, preferences),
+            None => view_add_time_entry_button(client_id, project_id),
+        },
         project
             .time_entries
             .iter()
             .rev()
             .map(|(time_entry_id, time_entry)| {
-                view_time_entry(client_id, project_id, *time_entry_id, time_entry)
+                view_time_entry(
+                    client_id,
+                    project_id,
+                    *time_entry_id,
+                    time_entry,
+                    time_entry.stopped.is_some()
+                        && previous_mergeable_time_entry(project, *time_entry_id).is_some(),
+                    warnings.get(time_entry_id).map(Vec::as_slice).unwrap_or_default(),
+                    selected_time_entries.contains(time_entry_id),
+                    project_options,
+                    preferences,
+                )
             }),
     ]
 }
 
+fn view_add_time_entry_button(client_id: ClientId, project_id: ProjectId) -> Node<Msg> {
+    div![
+        C!["level", "is-mobile"],
+        button![
+            C!["button", "is-small", "is-primary", "is-light", "is-rounded"],
+            style! {
+                St::MarginLeft => "auto",
+                St::MarginRight => "auto",
+            },
+            ev(Ev::Click, move |_| Msg::OpenNewTimeEntryForm(client_id, project_id)),
+            span![C!["icon"], i![C!["fas", "fa-plus"]]],
+            span!["Add entry"],
+        ],
+    ]
+}
+
+fn view_new_time_entry_form(
+    client_id: ClientId,
+    project_id: ProjectId,
+    form: &NewTimeEntryForm,
+    preferences: &Preferences,
+) -> Node<Msg> {
+    let date_example = preferences.date_format.label();
+    let time_example = preferences.clock_format.example();
+    let field = move |label_text: &str, placeholder: &str, value: &str, field: NewTimeEntryField| {
+        div![
+            C!["field"],
+            label![C!["label", "is-small"], label_text],
+            div![
+                C!["control"],
+                input![
+                    C!["input", "is-small"],
+                    attrs! {
+                        At::Value => value,
+                        At::Placeholder => placeholder,
+                    },
+                    input_ev(Ev::Input, move |value| Msg::NewTimeEntryFieldChanged(
+                        client_id, project_id, field, value
+                    )),
+                ],
+            ],
+        ]
+    };
+
+    div![
+        C!["box", "has-background-light"],
+        field("Name", "", &form.name, NewTimeEntryField::Name),
+        div![
+            C!["columns", "is-mobile"],
+            div![
+                C!["column"],
+                field("Start date", date_example, &form.started_date, NewTimeEntryField::StartedDate),
+            ],
+            div![
+                C!["column"],
+                field("Start time", time_example, &form.started_time, NewTimeEntryField::StartedTime),
+            ],
+        ],
+        if form.use_duration {
+            field("Duration", "1:30", &form.duration, NewTimeEntryField::Duration)
+        } else {
+            div![
+                C!["columns", "is-mobile"],
+                div![
+                    C!["column"],
+                    field("End date", date_example, &form.stopped_date, NewTimeEntryField::StoppedDate),
+                ],
+                div![
+                    C!["column"],
+                    field("End time", time_example, &form.stopped_time, NewTimeEntryField::StoppedTime),
+                ],
+            ]
+        },
+        div![
+            C!["field"],
+            label![
+                C!["checkbox", "is-size-7"],
+                input![
+                    C!["mr-2"],
+                    attrs! {
+                        At::Type => "checkbox",
+                        At::Checked => form.use_duration.as_at_value(),
+                    },
+                    ev(Ev::Change, move |_| Msg::ToggleNewTimeEntryDuration(
+                        client_id, project_id
+                    )),
+                ],
+                "Start plus duration",
+            ],
+        ],
+        form.error
+            .as_ref()
+            .map(|error| p![C!["help", "is-danger", "mb-3"], error]),
+        form.overlap_warning
+            .as_ref()
+            .map(|warning| p![C!["help", "is-warning", "mb-3"], warning]),
+        div![
+            C!["buttons", "is-right"],
+            button![
+                C!["button", "is-small", "is-rounded"],
+                ev(Ev::Click, move |_| Msg::CloseNewTimeEntryForm(client_id, project_id)),
+                "Cancel",
+            ],
+            button![
+                C![
+                    "button",
+                    "is-small",
+                    "is-rounded",
+                    if form.overlap_warning.is_some() {
+                        "is-warning"
+                    } else {
+                        "is-primary"
+                    }
+                ],
+                ev(Ev::Click, move |_| Msg::SaveNewTimeEntry(client_id, project_id)),
+                if form.overlap_warning.is_some() {
+                    "Save anyway"
+                } else {
+                    "Save"
+                },
+            ],
+        ],
+    ]
+}
+
+fn view_focus_button(client_id: ClientId, project_id: ProjectId) -> Node<Msg> {
+    button![
+        C!["button", "is-link", "is-light", "is-rounded"],
+        ev(Ev::Click, move |_| Msg::StartFocusSession(client_id, project_id)),
+        span![C!["icon"], i![C!["fas", "fa-hourglass-half"]]],
+        span!["Focus"],
+    ]
+}
+
 fn view_start_stop_button(client_id: ClientId, project_id: ProjectId, started: bool) -> Node<Msg> {
     div![
         C!["level", "is-mobile"],
@@ -500,6 +2316,11 @@ fn view_time_entry(
     project_id: ProjectId,
     time_entry_id: TimeEntryId,
     time_entry: &TimeEntry,
+    mergeable: bool,
+    warnings: &[(String, Option<analysis::Fix>)],
+    selected: bool,
+    project_options: &ProjectOptions,
+    preferences: &Preferences,
 ) -> Node<Msg> {
     let active = time_entry.stopped.is_none();
     let stopped = time_entry
@@ -536,7 +2357,7 @@ fn view_time_entry(
                     St::BorderBottom => format!("{} {} {}", "solid", if active { LINK_COLOR } else { PRIMARY_COLOR }, px(2)),
                     St::MaxWidth => percent(85),
                 },
-                attrs! {At::Value => time_entry.name},
+                attrs! {At::Value => time_entry.name_change.as_ref().unwrap_or(&time_entry.name)},
                 input_ev(Ev::Input, move |name| Msg::TimeEntryNameChanged(
                     client_id,
                     project_id,
@@ -563,7 +2384,8 @@ fn view_time_entry(
                 time_entry_id,
                 &duration,
                 time_entry.change.as_ref(),
-                active
+                active,
+                preferences
             )
         ],
         div![
@@ -574,7 +2396,8 @@ fn view_time_entry(
                 time_entry_id,
                 time_entry.change.as_ref(),
                 active,
-                &time_entry.started
+                &time_entry.started,
+                preferences
             ),
             div![
                 C!["is-hidden-mobile"],
@@ -584,7 +2407,8 @@ fn view_time_entry(
                     time_entry_id,
                     &duration,
                     time_entry.change.as_ref(),
-                    active
+                    active,
+                    preferences
                 ),
             ],
             view_stopped(
@@ -593,9 +2417,220 @@ fn view_time_entry(
                 time_entry_id,
                 time_entry.change.as_ref(),
                 active,
-                &stopped
+                &stopped,
+                preferences
             ),
         ],
+        time_entry.change_error.as_ref().map(|error| {
+            p![C!["help", "is-danger", "has-text-centered", "is-size-6"], error]
+        }),
+        match &time_entry.split_form {
+            Some(split_form) => view_split_form(client_id, project_id, time_entry_id, split_form),
+            None => div![
+                C!["level", "is-mobile", "mt-2", "mb-0"],
+                div![
+                    C!["level-left"],
+                    label![
+                        C!["checkbox", "level-item"],
+                        input![
+                            C!["mr-2"],
+                            attrs! {
+                                At::Type => "checkbox",
+                                At::Checked => selected.as_at_value(),
+                            },
+                            ev(Ev::Change, move |_| Msg::ToggleTimeEntrySelection(
+                                time_entry_id
+                            )),
+                        ],
+                        "Select",
+                    ],
+                    div![
+                        C!["level-item"],
+                        view_project_picker(
+                            project_options.iter().filter(|(other_client_id, other_project_id, _)| {
+                                (*other_client_id, *other_project_id) != (client_id, project_id)
+                            }),
+                            move |target_client_id, target_project_id| Msg::MoveTimeEntries(
+                                vec![time_entry_id],
+                                target_client_id,
+                                target_project_id
+                            ),
+                        ),
+                    ],
+                ],
+                div![
+                    C!["level-right"],
+                    view_split_and_merge_buttons(
+                        client_id,
+                        project_id,
+                        time_entry_id,
+                        mergeable,
+                        active,
+                    ),
+                ],
+            ],
+        },
+        warnings.iter().map(|(message, fix)| {
+            view_time_entry_warning(
+                client_id,
+                project_id,
+                time_entry_id,
+                message,
+                *fix,
+                active,
+                preferences,
+            )
+        }),
+    ]
+}
+
+fn view_split_and_merge_buttons(
+    client_id: ClientId,
+    project_id: ProjectId,
+    time_entry_id: TimeEntryId,
+    mergeable: bool,
+    for_active_time_entry: bool,
+) -> Node<Msg> {
+    let button_class = if for_active_time_entry {
+        "is-link"
+    } else {
+        "is-primary"
+    };
+    div![
+        C!["buttons", "is-right", "mb-0"],
+        IF!(mergeable => button![
+            C!["button", "is-small", "is-rounded", button_class],
+            ev(Ev::Click, move |_| Msg::MergeWithPrevious(
+                client_id,
+                project_id,
+                time_entry_id
+            )),
+            span![C!["icon"], i![C!["fas", "fa-compress-alt"]]],
+            span!["Merge with previous"],
+        ]),
+        button![
+            C!["button", "is-small", "is-rounded", button_class],
+            ev(Ev::Click, move |_| Msg::OpenSplitForm(
+                client_id,
+                project_id,
+                time_entry_id
+            )),
+            span![C!["icon"], i![C!["fas", "fa-cut"]]],
+            span!["Split"],
+        ],
+    ]
+}
+
+fn view_split_form(
+    client_id: ClientId,
+    project_id: ProjectId,
+    time_entry_id: TimeEntryId,
+    split_form: &SplitForm,
+) -> Node<Msg> {
+    div![
+        C!["box", "has-background-light", "mt-3"],
+        div![
+            C!["field", "is-grouped"],
+            div![
+                C!["control"],
+                label![C!["label", "is-small"], "Split at"],
+                input![
+                    C!["input", "is-small", IF!(split_form.error.is_some() => "is-danger")],
+                    attrs! {At::Value => split_form.time},
+                    input_ev(Ev::Input, move |time| Msg::SplitTimeChanged(
+                        client_id,
+                        project_id,
+                        time_entry_id,
+                        time
+                    )),
+                ],
+            ],
+            div![
+                C!["control", "is-expanded"],
+                label![C!["label", "is-small"], "Second entry name"],
+                input![
+                    C!["input", "is-small"],
+                    attrs! {At::Value => split_form.name},
+                    input_ev(Ev::Input, move |name| Msg::SplitNameChanged(
+                        client_id,
+                        project_id,
+                        time_entry_id,
+                        name
+                    )),
+                ],
+            ],
+        ],
+        split_form
+            .error
+            .as_ref()
+            .map(|error| p![C!["help", "is-danger", "mb-3"], error]),
+        div![
+            C!["buttons", "is-right"],
+            button![
+                C!["button", "is-small", "is-rounded"],
+                ev(Ev::Click, move |_| Msg::CloseSplitForm(
+                    client_id,
+                    project_id,
+                    time_entry_id
+                )),
+                "Cancel",
+            ],
+            button![
+                C!["button", "is-small", "is-rounded", "is-primary"],
+                ev(Ev::Click, move |_| Msg::SplitTimeEntry(
+                    client_id,
+                    project_id,
+                    time_entry_id
+                )),
+                "Split",
+            ],
+        ],
+    ]
+}
+
+fn view_time_entry_warning(
+    client_id: ClientId,
+    project_id: ProjectId,
+    time_entry_id: TimeEntryId,
+    message: &str,
+    fix: Option<analysis::Fix>,
+    for_active_time_entry: bool,
+    preferences: &Preferences,
+) -> Node<Msg> {
+    div![
+        C!["level", "is-mobile", "mt-2", "mb-0"],
+        span![
+            C![
+                "is-size-6",
+                if for_active_time_entry {
+                    "has-text-danger"
+                } else {
+                    "has-text-warning"
+                }
+            ],
+            span![C!["icon"], i![C!["fas", "fa-exclamation-triangle"]]],
+            message,
+        ],
+        fix.map(|fix| {
+            let label = match fix {
+                analysis::Fix::TrimStopped(stopped) => {
+                    format!("Stop at {}", preferences.format_time(&stopped))
+                }
+                analysis::Fix::DelayStarted(started) => {
+                    format!("Start at {}", preferences.format_time(&started))
+                }
+            };
+            button![
+                C!["button", "is-small", "is-warning", "is-rounded"],
+                ev(Ev::Click, move |_| Msg::ApplyTimeEntryFix(
+                    client_id,
+                    project_id,
+                    time_entry_id,
+                    fix
+                )),
+                label,
+            ]
+        }),
     ]
 }
 
@@ -606,6 +2641,7 @@ fn view_started(
     time_entry_change: Option<&TimeEntryChange>,
     for_active_time_entry: bool,
     started: &chrono::DateTime<chrono::Local>,
+    preferences: &Preferences,
 ) -> Node<Msg> {
     div![
         C!["is-flex"],
@@ -632,7 +2668,7 @@ fn view_started(
                 At::Value => if let Some(TimeEntryChange::StartedDate(date)) = time_entry_change {
                     date.to_owned()
                 } else {
-                    started.format("%F").to_string()
+                    preferences.format_date(started)
                 }
             },
             input_ev(Ev::Input, move |date| Msg::TimeEntryStartedDateChanged(
@@ -670,7 +2706,7 @@ fn view_started(
                 At::Value => if let Some(TimeEntryChange::StartedTime(time)) = time_entry_change {
                     time.to_owned()
                 } else {
-                    started.format("%X").to_string()
+                    preferences.format_time(started)
                 }
             },
             input_ev(Ev::Input, move |time| Msg::TimeEntryStartedTimeChanged(
@@ -695,6 +2731,7 @@ fn view_stopped(
     time_entry_change: Option<&TimeEntryChange>,
     for_active_time_entry: bool,
     stopped: &chrono::DateTime<chrono::Local>,
+    preferences: &Preferences,
 ) -> Node<Msg> {
     div![
         C!["is-flex"],
@@ -724,7 +2761,7 @@ fn view_stopped(
                 At::Value => if let Some(TimeEntryChange::StoppedDate(date)) = time_entry_change {
                     date.to_owned()
                 } else {
-                    stopped.format("%F").to_string()
+                    preferences.format_date(stopped)
                 }
             },
             input_ev(Ev::Input, move |date| Msg::TimeEntryStoppedDateChanged(
@@ -765,7 +2802,7 @@ fn view_stopped(
                 At::Value => if let Some(TimeEntryChange::StoppedTime(time)) = time_entry_change {
                     time.to_owned()
                 } else {
-                    stopped.format("%X").to_string()
+                    preferences.format_time(stopped)
                 }
             },
             input_ev(Ev::Input, move |time| Msg::TimeEntryStoppedTimeChanged(
@@ -790,15 +2827,8 @@ fn view_duration(
     duration: &chrono::Duration,
     time_entry_change: Option<&TimeEntryChange>,
     for_active_time_entry: bool,
+    preferences: &Preferences,
 ) -> Node<Msg> {
-    let num_seconds = duration.num_seconds();
-
-    let negative = num_seconds < 0;
-    let num_seconds = num_seconds.abs();
-    let hours = num_seconds / 3600;
-    let minutes = num_seconds % 3600 / 60;
-    let seconds = num_seconds % 60;
-
     input![
         C![
             "input",
@@ -826,7 +2856,7 @@ fn view_duration(
             At::Value => if let Some(TimeEntryChange::Duration(duration)) = time_entry_change {
                 duration.to_owned()
             } else {
-                format!("{}{}:{:02}:{:02}", if negative { "-" } else { "" }, hours, minutes, seconds)
+                preferences.format_duration(duration)
             }
         },
         input_ev(Ev::Input, move |duration| Msg::TimeEntryDurationChanged(
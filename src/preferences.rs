@@ -0,0 +1,433 @@
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+use chrono::{prelude::*, Duration, FixedOffset, TimeZone as _};
+
+const STORAGE_KEY: &str = "preferences";
+
+/// Idle thresholds offered in the settings.
+pub const IDLE_THRESHOLD_MINUTES: [u32; 5] = [5, 10, 15, 30, 60];
+
+// ------ Preferences ------
+
+/// Per-user display preferences, stored in the local storage.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Preferences {
+    pub time_zone: TimeZone,
+    pub clock_format: ClockFormat,
+    pub date_format: DateFormat,
+    pub week_start: WeekStart,
+    pub duration_format: DurationFormat,
+    /// Starting a timer stops the running ones.
+    pub single_active_timer: bool,
+    /// Editing `started` of a stopped time entry moves `stopped` too.
+    pub keep_duration: bool,
+    /// Running timers ask about the idle time after so many minutes without activity.
+    /// `None` disables the detection.
+    pub idle_threshold_minutes: Option<u32>,
+    pub pomodoro: PomodoroSettings,
+    pub working_hours: WorkingHours,
+}
+
+impl Preferences {
+    pub fn load() -> Self {
+        LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Err(error) = LocalStorage::insert(STORAGE_KEY, self) {
+            error!("Preferences cannot be saved!", error);
+        }
+    }
+
+    pub fn to_time_zone(&self, date_time: &DateTime<Local>) -> DateTime<FixedOffset> {
+        let offset = match self.time_zone {
+            TimeZone::Local => *date_time.offset(),
+            TimeZone::UtcOffset { minutes } => FixedOffset::east(minutes * 60),
+        };
+        date_time.with_timezone(&offset)
+    }
+
+    /// Interprets `date_time` as a time in the display time zone.
+    pub fn from_time_zone(&self, date_time: NaiveDateTime) -> Result<DateTime<Local>, String> {
+        match self.time_zone {
+            TimeZone::Local => Local.from_local_datetime(&date_time).single(),
+            TimeZone::UtcOffset { minutes } => FixedOffset::east(minutes * 60)
+                .from_local_datetime(&date_time)
+                .single()
+                .map(|date_time| date_time.with_timezone(&Local)),
+        }
+        .ok_or_else(|| format!("{} doesn't exist in the selected time zone.", date_time))
+    }
+
+    /// Replaces the date of `date_time` as seen in the display time zone.
+    pub fn with_date(
+        &self,
+        date_time: &DateTime<Local>,
+        date: NaiveDate,
+    ) -> Result<DateTime<Local>, String> {
+        let time = self.to_time_zone(date_time).time();
+        self.from_time_zone(date.and_time(time))
+    }
+
+    /// Replaces the time of `date_time` as seen in the display time zone.
+    pub fn with_time(
+        &self,
+        date_time: &DateTime<Local>,
+        time: NaiveTime,
+    ) -> Result<DateTime<Local>, String> {
+        let date = self.to_time_zone(date_time).date().naive_local();
+        self.from_time_zone(date.and_time(time))
+    }
+
+    pub fn parse_date_time(&self, date: &str, time: &str) -> Result<DateTime<Local>, String> {
+        let date = self.parse_date(date)?;
+        let time = self.parse_time(time)?;
+        self.from_time_zone(date.and_time(time))
+    }
+
+    pub fn parse_date(&self, date: &str) -> Result<NaiveDate, String> {
+        let date = date.trim();
+        NaiveDate::parse_from_str(date, self.date_format.pattern()).map_err(|_| {
+            format!(
+                "\"{}\" is not a date like {}.",
+                date,
+                self.date_format.label()
+            )
+        })
+    }
+
+    /// Seconds are optional.
+    pub fn parse_time(&self, time: &str) -> Result<NaiveTime, String> {
+        let time = time.trim();
+        let patterns: &[&str] = match self.clock_format {
+            ClockFormat::H24 => &["%H:%M:%S", "%H:%M"],
+            ClockFormat::H12 => &["%I:%M:%S %p", "%I:%M %p"],
+        };
+        patterns
+            .iter()
+            .find_map(|pattern| NaiveTime::parse_from_str(time, pattern).ok())
+            .ok_or_else(|| {
+                format!(
+                    "\"{}\" is not a time like {}.",
+                    time,
+                    self.clock_format.example()
+                )
+            })
+    }
+
+    pub fn format_date(&self, date_time: &DateTime<Local>) -> String {
+        self.to_time_zone(date_time)
+            .format(self.date_format.pattern())
+            .to_string()
+    }
+
+    pub fn format_time(&self, date_time: &DateTime<Local>) -> String {
+        self.to_time_zone(date_time)
+            .format(self.clock_format.pattern())
+            .to_string()
+    }
+
+    pub fn format_duration(&self, duration: &Duration) -> String {
+        let num_seconds = duration.num_seconds();
+        let sign = if num_seconds < 0 { "-" } else { "" };
+        let num_seconds = num_seconds.abs();
+
+        match self.duration_format {
+            DurationFormat::HoursMinutesSeconds => format!(
+                "{}{}:{:02}:{:02}",
+                sign,
+                num_seconds / 3600,
+                num_seconds % 3600 / 60,
+                num_seconds % 60
+            ),
+            DurationFormat::DecimalHours => format!("{}{:.2}", sign, num_seconds as f64 / 3600.),
+        }
+    }
+
+    pub fn idle_threshold(&self) -> Option<Duration> {
+        self.idle_threshold_minutes
+            .map(|minutes| Duration::minutes(i64::from(minutes)))
+    }
+
+    /// Today's working hours in the display time zone, `None` on days off.
+    pub fn working_hours_on(
+        &self,
+        date_time: &DateTime<Local>,
+    ) -> Option<(DateTime<Local>, DateTime<Local>)> {
+        let date_time = self.to_time_zone(date_time);
+        let weekday = date_time.weekday().num_days_from_monday() as usize;
+        let working_day = self.working_hours.days[weekday]?;
+        // Minutes from midnight, so a working day can end at 24:00.
+        let midnight = date_time.date().naive_local().and_hms(0, 0, 0);
+        let at = |minutes: u32| {
+            let naive_date_time = midnight + Duration::minutes(i64::from(minutes));
+            self.from_time_zone(naive_date_time).ok()
+        };
+        Some((at(working_day.start_minutes)?, at(working_day.end_minutes)?))
+    }
+
+    pub fn first_day_of_week(&self) -> Weekday {
+        match self.week_start {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Saturday => Weekday::Sat,
+            WeekStart::Sunday => Weekday::Sun,
+        }
+    }
+
+    /// Midnight of the first day of the week of `date_time` in the display time zone.
+    pub fn start_of_week(&self, date_time: &DateTime<Local>) -> Result<DateTime<Local>, String> {
+        let date = self.to_time_zone(date_time).date().naive_local();
+        let days_since_week_start = (7 + date.weekday().num_days_from_monday()
+            - self.first_day_of_week().num_days_from_monday())
+            % 7;
+        let week_start = date - Duration::days(i64::from(days_since_week_start));
+        self.from_time_zone(week_start.and_hms(0, 0, 0))
+    }
+}
+
+// ------ PomodoroSettings ------
+
+/// Focus mode cycle of work intervals and breaks with a long break after every few work intervals.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct PomodoroSettings {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    pub work_intervals_before_long_break: u32,
+}
+
+impl Default for PomodoroSettings {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            work_intervals_before_long_break: 4,
+        }
+    }
+}
+
+impl PomodoroSettings {
+    pub fn work(&self) -> Duration {
+        Duration::minutes(i64::from(self.work_minutes))
+    }
+
+    pub fn short_break(&self) -> Duration {
+        Duration::minutes(i64::from(self.short_break_minutes))
+    }
+
+    pub fn long_break(&self) -> Duration {
+        Duration::minutes(i64::from(self.long_break_minutes))
+    }
+}
+
+// ------ WorkingHours ------
+
+/// Reminders to start a timer during the working hours and to stop it after them.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct WorkingHours {
+    pub reminders: bool,
+    /// Remind when no timer has been running for so many minutes.
+    pub reminder_after_minutes: u32,
+    /// Indexed by `Weekday::num_days_from_monday`, `None` is a day off.
+    pub days: [Option<WorkingDay>; 7],
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        let working_day = Some(WorkingDay::default());
+        Self {
+            reminders: false,
+            reminder_after_minutes: 15,
+            days: [working_day, working_day, working_day, working_day, working_day, None, None],
+        }
+    }
+}
+
+impl WorkingHours {
+    pub fn reminder_after(&self) -> Duration {
+        Duration::minutes(i64::from(self.reminder_after_minutes))
+    }
+}
+
+/// Minutes since midnight.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct WorkingDay {
+    pub start_minutes: u32,
+    pub end_minutes: u32,
+}
+
+impl Default for WorkingDay {
+    fn default() -> Self {
+        Self {
+            start_minutes: 9 * 60,
+            end_minutes: 17 * 60,
+        }
+    }
+}
+
+// ------ TimeZone ------
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TimeZone {
+    Local,
+    UtcOffset { minutes: i32 },
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self::Local
+    }
+}
+
+impl TimeZone {
+    /// `Local` followed by UTC offsets from -12:00 to +14:00 in 30 minute steps.
+    pub fn options() -> impl Iterator<Item = Self> {
+        let offsets = (-24..=28).map(|half_hours| Self::UtcOffset {
+            minutes: half_hours * 30,
+        });
+        std::iter::once(Self::Local).chain(offsets)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Local => "Browser time zone".to_owned(),
+            Self::UtcOffset { minutes } => format!(
+                "UTC{}{:02}:{:02}",
+                if *minutes < 0 { "-" } else { "+" },
+                minutes.abs() / 60,
+                minutes.abs() % 60
+            ),
+        }
+    }
+}
+
+// ------ ClockFormat ------
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ClockFormat {
+    H24,
+    H12,
+}
+
+impl Default for ClockFormat {
+    fn default() -> Self {
+        Self::H24
+    }
+}
+
+impl ClockFormat {
+    pub const ALL: [Self; 2] = [Self::H24, Self::H12];
+
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Self::H24 => "%H:%M:%S",
+            Self::H12 => "%I:%M:%S %p",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::H24 => "24-hour (17:30:00)",
+            Self::H12 => "12-hour (05:30:00 PM)",
+        }
+    }
+
+    pub fn example(&self) -> &'static str {
+        match self {
+            Self::H24 => "17:30:00",
+            Self::H12 => "05:30:00 PM",
+        }
+    }
+}
+
+// ------ DateFormat ------
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum DateFormat {
+    Iso,
+    DayMonthYear,
+    MonthDayYear,
+}
+
+impl Default for DateFormat {
+    fn default() -> Self {
+        Self::Iso
+    }
+}
+
+impl DateFormat {
+    pub const ALL: [Self; 3] = [Self::Iso, Self::DayMonthYear, Self::MonthDayYear];
+
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Self::Iso => "%F",
+            Self::DayMonthYear => "%d.%m.%Y",
+            Self::MonthDayYear => "%m/%d/%Y",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Iso => "2021-12-31",
+            Self::DayMonthYear => "31.12.2021",
+            Self::MonthDayYear => "12/31/2021",
+        }
+    }
+}
+
+// ------ WeekStart ------
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum WeekStart {
+    Monday,
+    Saturday,
+    Sunday,
+}
+
+impl Default for WeekStart {
+    fn default() -> Self {
+        Self::Monday
+    }
+}
+
+impl WeekStart {
+    pub const ALL: [Self; 3] = [Self::Monday, Self::Saturday, Self::Sunday];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Monday => "Monday",
+            Self::Saturday => "Saturday",
+            Self::Sunday => "Sunday",
+        }
+    }
+}
+
+// ------ DurationFormat ------
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum DurationFormat {
+    HoursMinutesSeconds,
+    DecimalHours,
+}
+
+impl Default for DurationFormat {
+    fn default() -> Self {
+        Self::HoursMinutesSeconds
+    }
+}
+
+impl DurationFormat {
+    pub const ALL: [Self; 2] = [Self::HoursMinutesSeconds, Self::DecimalHours];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::HoursMinutesSeconds => "h:mm:ss (1:30:00)",
+            Self::DecimalHours => "Decimal hours (1.50)",
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use seed::{prelude::*, *};
+
+use chrono::prelude::*;
+use ulid::Ulid;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::notification;
+use crate::preferences::Preferences;
+use crate::store::Store;
+
+// Projects offered by the reminder to start a timer.
+const QUICK_START_PROJECTS: usize = 3;
+
+// ------ Reminders ------
+
+/// Working hours reminders, they are checked by the app on every page.
+#[derive(Default)]
+pub struct Reminders {
+    pub reminder: Option<Reminder>,
+    // Every gap and time entry is reminded about only once.
+    reminded_no_timer_since: Option<DateTime<Local>>,
+    reminded_running_after_hours: BTreeSet<Ulid>,
+}
+
+/// In-app banner of the working hours reminders, in case notifications aren't allowed.
+pub enum Reminder {
+    NoTimerRunning {
+        since: DateTime<Local>,
+    },
+    RunningAfterHours {
+        client_id: Ulid,
+        project_id: Ulid,
+        time_entry_id: Ulid,
+        working_hours_ended: DateTime<Local>,
+    },
+}
+
+impl Reminders {
+    /// Reminds to start a timer during the working hours and to stop the ones running after them.
+    ///
+    /// Returns `true` if the reminder banner has changed.
+    pub fn check<Ms: 'static>(
+        &mut self,
+        store: &Store,
+        now: DateTime<Local>,
+        preferences: &Preferences,
+        orders: &mut impl Orders<Ms>,
+    ) -> bool {
+        if store.remote_data().loaded().is_none() || not(preferences.working_hours.reminders) {
+            return self.reminder.take().is_some();
+        }
+        let running_time_entries = store
+            .time_entries
+            .iter()
+            .filter(|(_, time_entry)| time_entry.stopped.is_none())
+            .filter_map(|(time_entry_id, time_entry)| {
+                let project = store.projects.get(&time_entry.project_id)?;
+                Some((project.client_id, time_entry.project_id, *time_entry_id, time_entry))
+            })
+            .collect::<Vec<_>>();
+
+        let outdated = match &self.reminder {
+            Some(Reminder::NoTimerRunning { .. }) => not(running_time_entries.is_empty()),
+            Some(Reminder::RunningAfterHours { time_entry_id, .. }) => running_time_entries
+                .iter()
+                .all(|(_, _, running_id, _)| running_id != time_entry_id),
+            None => false,
+        };
+        let mut changed = outdated && self.reminder.take().is_some();
+
+        let (working_hours_started, working_hours_ended) =
+            match preferences.working_hours_on(&now) {
+                Some(working_hours) => working_hours,
+                None => return changed,
+            };
+
+        if running_time_entries.is_empty() {
+            if now < working_hours_started || now >= working_hours_ended {
+                return changed;
+            }
+            let last_stopped = store
+                .time_entries
+                .values()
+                .filter_map(|time_entry| time_entry.stopped)
+                .max();
+            let since = last_stopped.map_or(working_hours_started, |last_stopped| {
+                last_stopped.max(working_hours_started)
+            });
+            let reminder_after = preferences.working_hours.reminder_after();
+            if now - since >= reminder_after && self.reminded_no_timer_since != Some(since) {
+                self.reminded_no_timer_since = Some(since);
+                self.reminder = Some(Reminder::NoTimerRunning { since });
+                changed = true;
+                orders.perform_cmd(notification::show(
+                    "No timer is running".to_owned(),
+                    format!(
+                        "Nothing has been tracked since {}.",
+                        preferences.format_time(&since)
+                    ),
+                ));
+            }
+        } else if now >= working_hours_ended {
+            for (client_id, project_id, time_entry_id, time_entry) in running_time_entries {
+                // Timers started after the working hours are deliberate overtime.
+                if time_entry.started >= working_hours_ended
+                    || not(self.reminded_running_after_hours.insert(time_entry_id))
+                {
+                    continue;
+                }
+                self.reminder = Some(Reminder::RunningAfterHours {
+                    client_id,
+                    project_id,
+                    time_entry_id,
+                    working_hours_ended,
+                });
+                changed = true;
+                let project_name = store
+                    .projects
+                    .get(&project_id)
+                    .map(|project| project.name.as_str())
+                    .unwrap_or_default();
+                orders.perform_cmd(notification::show(
+                    "A timer is still running".to_owned(),
+                    format!(
+                        "{} is running although the working hours ended at {}.",
+                        project_name,
+                        preferences.format_time(&working_hours_ended)
+                    ),
+                ));
+            }
+        }
+        changed
+    }
+}
+
+/// Projects ordered by their latest time entry, the most recent first.
+fn recent_projects(store: &Store) -> Vec<(Ulid, Ulid, &str)> {
+    let mut last_started = BTreeMap::new();
+    for time_entry in store.time_entries.values() {
+        let started = last_started.entry(time_entry.project_id).or_insert(time_entry.started);
+        *started = time_entry.started.max(*started);
+    }
+    let mut projects = last_started
+        .into_iter()
+        .filter_map(|(project_id, last_started)| {
+            let project = store.projects.get(&project_id)?;
+            Some((last_started, project.client_id, project_id, project.name.as_str()))
+        })
+        .collect::<Vec<_>>();
+    projects.sort_by_key(|(last_started, ..)| std::cmp::Reverse(*last_started));
+    projects
+        .into_iter()
+        .map(|(_, client_id, project_id, name)| (client_id, project_id, name))
+        .collect()
+}
+
+// ------ view ------
+
+pub fn view<Ms: 'static>(
+    reminder: &Reminder,
+    store: &Store,
+    preferences: &Preferences,
+    on_start: fn(Ulid, Ulid) -> Ms,
+    on_stop: fn(Ulid, Ulid) -> Ms,
+    on_dismiss: fn() -> Ms,
+) -> Node<Ms> {
+    let (message, buttons) = match reminder {
+        Reminder::NoTimerRunning { since } => (
+            format!(
+                "No timer is running, nothing has been tracked since {}.",
+                preferences.format_time(since)
+            ),
+            recent_projects(store)
+                .into_iter()
+                .take(QUICK_START_PROJECTS)
+                .map(|(client_id, project_id, name)| {
+                    button![
+                        C!["button", "is-small", "is-rounded", "is-primary"],
+                        ev(Ev::Click, move |_| on_start(client_id, project_id)),
+                        span![C!["icon"], i![C!["fas", "fa-play"]]],
+                        span![name],
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Reminder::RunningAfterHours {
+            client_id,
+            project_id,
+            working_hours_ended,
+            ..
+        } => {
+            let (client_id, project_id) = (*client_id, *project_id);
+            let project_name = store
+                .projects
+                .get(&project_id)
+                .map(|project| project.name.as_str())
+                .unwrap_or_default();
+            (
+                format!(
+                    "{} is still running although the working hours ended at {}.",
+                    project_name,
+                    preferences.format_time(working_hours_ended)
+                ),
+                vec![button![
+                    C!["button", "is-small", "is-rounded", "is-warning"],
+                    ev(Ev::Click, move |_| on_stop(client_id, project_id)),
+                    "Stop",
+                ]],
+            )
+        }
+    };
+    div![
+        C!["notification", "is-info", "mx-6", "mt-5"],
+        button![C!["delete"], ev(Ev::Click, move |_| on_dismiss())],
+        p![message],
+        div![C!["buttons", "mt-3"], buttons],
+    ]
+}
@@ -0,0 +1,104 @@
+use seed::{prelude::*, *};
+
+use std::rc::Rc;
+
+use crate::graphql::GraphQLError;
+
+// ------ RemoteData ------
+
+/// Data loaded from the server.
+///
+/// The error is shared because all pages built from one failed fetch show the same error.
+#[derive(Debug)]
+pub enum RemoteData<T> {
+    NotAsked,
+    Loading,
+    Loaded(T),
+    /// Being fetched again, the old data are still shown.
+    Reloading(T),
+    Failed(Rc<GraphQLError>),
+}
+
+impl<T> RemoteData<T> {
+    /// Returns the data while they are loaded or reloading.
+    pub fn loaded(&self) -> Option<&T> {
+        match self {
+            Self::Loaded(data) | Self::Reloading(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn loaded_mut(&mut self) -> Option<&mut T> {
+        match self {
+            Self::Loaded(data) | Self::Reloading(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn as_ref(&self) -> RemoteData<&T> {
+        match self {
+            Self::NotAsked => RemoteData::NotAsked,
+            Self::Loading => RemoteData::Loading,
+            Self::Loaded(data) => RemoteData::Loaded(data),
+            Self::Reloading(data) => RemoteData::Reloading(data),
+            Self::Failed(error) => RemoteData::Failed(Rc::clone(error)),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> RemoteData<U> {
+        self.and_then(|data| RemoteData::Loaded(f(data)))
+    }
+
+    /// Chains a computation that may fail or still be loading.
+    ///
+    /// `Reloading` is preserved when `f` returns `Loaded`.
+    pub fn and_then<U>(self, f: impl FnOnce(T) -> RemoteData<U>) -> RemoteData<U> {
+        match self {
+            Self::NotAsked => RemoteData::NotAsked,
+            Self::Loading => RemoteData::Loading,
+            Self::Loaded(data) => f(data),
+            Self::Reloading(data) => match f(data) {
+                RemoteData::Loaded(data) => RemoteData::Reloading(data),
+                remote_data => remote_data,
+            },
+            Self::Failed(error) => RemoteData::Failed(error),
+        }
+    }
+}
+
+// ------ view ------
+
+/// Shows a progress bar while loading, an error with a Retry button on failure, or the data.
+///
+/// Reloading data are shown under a thin progress bar.
+pub fn view<T, Ms: 'static>(
+    remote_data: &RemoteData<T>,
+    on_retry: impl FnOnce() -> Ms + Clone + 'static,
+    view_data: impl FnOnce(&T) -> Vec<Node<Ms>>,
+) -> Vec<Node<Ms>> {
+    match remote_data {
+        RemoteData::NotAsked | RemoteData::Loading => {
+            progress![C!["progress", "is-link", "mt-6"]].into_nodes()
+        }
+        RemoteData::Loaded(data) => view_data(data),
+        RemoteData::Reloading(data) => {
+            let mut nodes = vec![progress![C!["progress", "is-small", "is-link", "mt-6"]]];
+            nodes.extend(view_data(data));
+            nodes
+        }
+        RemoteData::Failed(error) => div![
+            C!["message", "is-danger", "mt-6"],
+            div![
+                C!["message-body"],
+                p![C!["has-text-weight-bold"], "Loading failed"],
+                p![C!["mb-3"], error.to_string()],
+                button![
+                    C!["button", "is-danger"],
+                    ev(Ev::Click, move |_| on_retry()),
+                    "Retry",
+                ],
+            ],
+        ]
+        .into_nodes(),
+    }
+}
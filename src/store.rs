@@ -0,0 +1,539 @@
+use chrono::{prelude::*, Duration};
+use ulid::Ulid;
+
+use cynic::QueryBuilder;
+
+use std::collections::BTreeMap;
+use std::convert::identity;
+use std::rc::Rc;
+
+use crate::graphql;
+use crate::remote_data::RemoteData;
+
+pub type ClientId = Ulid;
+pub type ProjectId = Ulid;
+pub type TimeEntryId = Ulid;
+pub type TimeBlockId = Ulid;
+pub type InvoiceId = Ulid;
+
+// ------ Events ------
+
+/// Notified when a fetch of the store has finished, successfully or not.
+///
+/// Pages subscribe to it and rebuild their models from the store.
+#[derive(Debug, Clone, Copy)]
+pub struct Changed;
+
+/// Notified by pages when the store may no longer match the server, e.g. after a replayed save.
+#[derive(Debug, Clone, Copy)]
+pub struct Invalidate;
+
+// ------ Store ------
+
+/// App-wide entities of the logged-in user, keyed by their ids.
+///
+/// Every entity refers to its parent so the maps stay flat.
+/// Pages read from the store and write their saved changes back to it.
+#[derive(Debug, Clone, Default)]
+pub struct Store {
+    pub status: Status,
+    pub clients: BTreeMap<ClientId, Client>,
+    pub projects: BTreeMap<ProjectId, Project>,
+    pub time_entries: BTreeMap<TimeEntryId, TimeEntry>,
+    pub time_blocks: BTreeMap<TimeBlockId, TimeBlock>,
+    pub invoices: BTreeMap<InvoiceId, Invoice>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Status {
+    NotLoaded,
+    Loading,
+    Loaded,
+    /// Invalidated, the current entities are shown until the fetch finishes.
+    Reloading,
+    /// The first fetch failed, there are no entities to show.
+    Failed(Rc<graphql::GraphQLError>),
+}
+
+impl Default for Status {
+    fn default() -> Self {
+        Self::NotLoaded
+    }
+}
+
+impl Store {
+    /// The store as `RemoteData`, pages `map` it to their own models.
+    pub fn remote_data(&self) -> RemoteData<&Self> {
+        match &self.status {
+            Status::NotLoaded => RemoteData::NotAsked,
+            Status::Loading => RemoteData::Loading,
+            Status::Loaded => RemoteData::Loaded(self),
+            Status::Reloading => RemoteData::Reloading(self),
+            Status::Failed(error) => RemoteData::Failed(Rc::clone(error)),
+        }
+    }
+
+    pub fn projects_of(
+        &self,
+        client_id: ClientId,
+    ) -> impl Iterator<Item = (ProjectId, &Project)> {
+        self.projects
+            .iter()
+            .filter(move |(_, project)| project.client_id == client_id)
+            .map(|(project_id, project)| (*project_id, project))
+    }
+
+    pub fn time_entries_of(
+        &self,
+        project_id: ProjectId,
+    ) -> impl Iterator<Item = (TimeEntryId, &TimeEntry)> {
+        self.time_entries
+            .iter()
+            .filter(move |(_, time_entry)| time_entry.project_id == project_id)
+            .map(|(time_entry_id, time_entry)| (*time_entry_id, time_entry))
+    }
+
+    pub fn time_blocks_of(
+        &self,
+        client_id: ClientId,
+    ) -> impl Iterator<Item = (TimeBlockId, &TimeBlock)> {
+        self.time_blocks
+            .iter()
+            .filter(move |(_, time_block)| time_block.client_id == client_id)
+            .map(|(time_block_id, time_block)| (*time_block_id, time_block))
+    }
+
+    /// Removes the client with all its descendants.
+    pub fn remove_client(&mut self, client_id: ClientId) -> Change {
+        let mut change = Change::default();
+        if let Some(client) = self.clients.remove(&client_id) {
+            change.clients.push(Diff::removed(client_id, client));
+        }
+
+        let project_ids = self
+            .projects_of(client_id)
+            .map(|(project_id, _)| project_id)
+            .collect::<Vec<_>>();
+        for project_id in project_ids {
+            change.extend(self.remove_project(project_id));
+        }
+
+        let time_block_ids = self
+            .time_blocks_of(client_id)
+            .map(|(time_block_id, _)| time_block_id)
+            .collect::<Vec<_>>();
+        for time_block_id in time_block_ids {
+            change.extend(self.remove_time_block(time_block_id));
+        }
+        change
+    }
+
+    /// Removes the project with its time entries.
+    pub fn remove_project(&mut self, project_id: ProjectId) -> Change {
+        let mut change = Change::default();
+        if let Some(project) = self.projects.remove(&project_id) {
+            change.projects.push(Diff::removed(project_id, project));
+        }
+
+        let time_entry_ids = self
+            .time_entries_of(project_id)
+            .map(|(time_entry_id, _)| time_entry_id)
+            .collect::<Vec<_>>();
+        for time_entry_id in time_entry_ids {
+            if let Some(time_entry) = self.time_entries.remove(&time_entry_id) {
+                change.time_entries.push(Diff::removed(time_entry_id, time_entry));
+            }
+        }
+        change
+    }
+
+    /// Removes the time block with its invoice.
+    pub fn remove_time_block(&mut self, time_block_id: TimeBlockId) -> Change {
+        let mut change = Change::default();
+        if let Some(time_block) = self.time_blocks.remove(&time_block_id) {
+            if let Some(invoice_id) = time_block.invoice_id {
+                if let Some(invoice) = self.invoices.remove(&invoice_id) {
+                    change.invoices.push(Diff::removed(invoice_id, invoice));
+                }
+            }
+            change.time_blocks.push(Diff::removed(time_block_id, time_block));
+        }
+        change
+    }
+
+    pub fn remove_invoice(&mut self, invoice_id: InvoiceId) -> Change {
+        let mut change = Change::default();
+        if let Some(invoice) = self.invoices.remove(&invoice_id) {
+            let before = Snapshot::default().time_block(self, invoice.time_block_id);
+            if let Some(time_block) = self.time_blocks.get_mut(&invoice.time_block_id) {
+                time_block.invoice_id = None;
+            }
+            change = self.change_since(before);
+            change.invoices.push(Diff::removed(invoice_id, invoice));
+        }
+        change
+    }
+}
+
+// ------ Change ------
+
+/// Entities modified by an optimistic update, with their values before and after it.
+///
+/// It's reverted when the update's mutation fails.
+#[derive(Debug, Default)]
+pub struct Change {
+    clients: Vec<Diff<ClientId, Client>>,
+    projects: Vec<Diff<ProjectId, Project>>,
+    time_entries: Vec<Diff<TimeEntryId, TimeEntry>>,
+    time_blocks: Vec<Diff<TimeBlockId, TimeBlock>>,
+    invoices: Vec<Diff<InvoiceId, Invoice>>,
+}
+
+#[derive(Debug)]
+struct Diff<K, V> {
+    id: K,
+    before: Option<V>,
+    after: Option<V>,
+}
+
+impl Change {
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+            && self.projects.is_empty()
+            && self.time_entries.is_empty()
+            && self.time_blocks.is_empty()
+            && self.invoices.is_empty()
+    }
+
+    /// Ids of the time entries modified by the change.
+    pub fn time_entry_ids(&self) -> impl Iterator<Item = TimeEntryId> + '_ {
+        self.time_entries.iter().map(|diff| diff.id)
+    }
+
+    pub fn extend(&mut self, other: Change) {
+        self.clients.extend(other.clients);
+        self.projects.extend(other.projects);
+        self.time_entries.extend(other.time_entries);
+        self.time_blocks.extend(other.time_blocks);
+        self.invoices.extend(other.invoices);
+    }
+}
+
+impl<K, V> Diff<K, V> {
+    fn removed(id: K, before: V) -> Self {
+        Self {
+            id,
+            before: Some(before),
+            after: None,
+        }
+    }
+}
+
+/// Entities an optimistic update is going to modify, with their values before it.
+///
+/// Entities that don't exist yet are stored as `None`, so added ones are reverted too.
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    clients: BTreeMap<ClientId, Option<Client>>,
+    projects: BTreeMap<ProjectId, Option<Project>>,
+    time_entries: BTreeMap<TimeEntryId, Option<TimeEntry>>,
+    time_blocks: BTreeMap<TimeBlockId, Option<TimeBlock>>,
+    invoices: BTreeMap<InvoiceId, Option<Invoice>>,
+}
+
+impl Snapshot {
+    pub fn client(mut self, store: &Store, client_id: ClientId) -> Self {
+        self.clients.insert(client_id, store.clients.get(&client_id).cloned());
+        self
+    }
+
+    pub fn project(mut self, store: &Store, project_id: ProjectId) -> Self {
+        self.projects.insert(project_id, store.projects.get(&project_id).cloned());
+        self
+    }
+
+    pub fn time_entries(
+        mut self,
+        store: &Store,
+        time_entry_ids: impl IntoIterator<Item = TimeEntryId>,
+    ) -> Self {
+        for time_entry_id in time_entry_ids {
+            self.time_entries
+                .insert(time_entry_id, store.time_entries.get(&time_entry_id).cloned());
+        }
+        self
+    }
+
+    pub fn time_block(mut self, store: &Store, time_block_id: TimeBlockId) -> Self {
+        self.time_blocks.insert(time_block_id, store.time_blocks.get(&time_block_id).cloned());
+        self
+    }
+
+    pub fn invoice(mut self, store: &Store, invoice_id: InvoiceId) -> Self {
+        self.invoices.insert(invoice_id, store.invoices.get(&invoice_id).cloned());
+        self
+    }
+}
+
+impl Store {
+    /// Compares the entities in the snapshot with their current values.
+    pub fn change_since(&self, before: Snapshot) -> Change {
+        Change {
+            clients: diff_map(before.clients, &self.clients),
+            projects: diff_map(before.projects, &self.projects),
+            time_entries: diff_map(before.time_entries, &self.time_entries),
+            time_blocks: diff_map(before.time_blocks, &self.time_blocks),
+            invoices: diff_map(before.invoices, &self.invoices),
+        }
+    }
+
+    /// Restores the entities from before the change.
+    ///
+    /// Entities modified again after the change keep their newer values,
+    /// so a failed request doesn't undo a later successful one.
+    pub fn revert(&mut self, change: Change) {
+        revert_map(&mut self.clients, change.clients);
+        revert_map(&mut self.projects, change.projects);
+        revert_map(&mut self.time_entries, change.time_entries);
+        revert_map(&mut self.time_blocks, change.time_blocks);
+        revert_map(&mut self.invoices, change.invoices);
+    }
+}
+
+fn diff_map<K: Ord, V: Clone + PartialEq>(
+    before: BTreeMap<K, Option<V>>,
+    after: &BTreeMap<K, V>,
+) -> Vec<Diff<K, V>> {
+    before
+        .into_iter()
+        .filter_map(|(id, before)| {
+            let after = after.get(&id);
+            if before.as_ref() == after {
+                return None;
+            }
+            Some(Diff {
+                id,
+                before,
+                after: after.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn revert_map<K: Ord, V: PartialEq>(map: &mut BTreeMap<K, V>, diffs: Vec<Diff<K, V>>) {
+    for diff in diffs {
+        if map.get(&diff.id) != diff.after.as_ref() {
+            continue;
+        }
+        match diff.before {
+            Some(value) => {
+                map.insert(diff.id, value);
+            }
+            None => {
+                map.remove(&diff.id);
+            }
+        }
+    }
+}
+
+// ------ Entities ------
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Client {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Project {
+    pub client_id: ClientId,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeEntry {
+    pub project_id: ProjectId,
+    pub name: String,
+    pub started: DateTime<Local>,
+    pub stopped: Option<DateTime<Local>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeBlock {
+    pub client_id: ClientId,
+    pub name: String,
+    pub status: TimeBlockStatus,
+    pub duration: Duration,
+    pub invoice_id: Option<InvoiceId>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TimeBlockStatus {
+    NonBillable,
+    Unpaid,
+    Paid,
+}
+
+impl From<graphql::types::TimeBlockStatus> for TimeBlockStatus {
+    fn from(status: graphql::types::TimeBlockStatus) -> Self {
+        match status {
+            graphql::types::TimeBlockStatus::NonBillable => Self::NonBillable,
+            graphql::types::TimeBlockStatus::Unpaid => Self::Unpaid,
+            graphql::types::TimeBlockStatus::Paid => Self::Paid,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invoice {
+    pub time_block_id: TimeBlockId,
+    pub custom_id: Option<String>,
+    pub url: Option<String>,
+}
+
+// ------ fetch ------
+
+/// Loads all entities of the user in one request.
+///
+/// Invalid records are skipped together with their descendants and returned as errors.
+pub async fn fetch(
+    graphql_config: &graphql::Config,
+) -> graphql::Result<(Store, Vec<graphql::GraphQLError>)> {
+    use graphql::queries::clients_with_all_descendants as query_mod;
+
+    let operation = query_mod::Query::build(query_mod::Arguments {
+        filter: graphql::types::ClientFilter::user(graphql_config.user_id()),
+    });
+    let clients = graphql::send_operation(graphql_config, operation)
+        .await?
+        .query_client
+        .ok_or_else(|| graphql::GraphQLError::missing_data("clients"))?
+        .into_iter()
+        .filter_map(identity);
+
+    let mut store = Store {
+        status: Status::Loaded,
+        ..Store::default()
+    };
+    let mut invalid_data = Vec::new();
+
+    for client in clients {
+        let client_id: ClientId = match parse_id("client", &client.id) {
+            Ok(client_id) => client_id,
+            Err(error) => {
+                invalid_data.push(error);
+                continue;
+            }
+        };
+        store.clients.insert(client_id, Client { name: client.name });
+
+        for project in client.projects {
+            let project_id: ProjectId = match parse_id("project", &project.id) {
+                Ok(project_id) => project_id,
+                Err(error) => {
+                    invalid_data.push(error);
+                    continue;
+                }
+            };
+            store.projects.insert(
+                project_id,
+                Project {
+                    client_id,
+                    name: project.name,
+                },
+            );
+
+            for time_entry in project.time_entries {
+                match time_entry_from_response(project_id, time_entry) {
+                    Ok((time_entry_id, time_entry)) => {
+                        store.time_entries.insert(time_entry_id, time_entry);
+                    }
+                    Err(error) => invalid_data.push(error),
+                }
+            }
+        }
+
+        for time_block in client.time_blocks {
+            let time_block_id: TimeBlockId = match parse_id("time_block", &time_block.id) {
+                Ok(time_block_id) => time_block_id,
+                Err(error) => {
+                    invalid_data.push(error);
+                    continue;
+                }
+            };
+            // The time block is still usable without its invoice.
+            let invoice_id = time_block.invoice.and_then(|invoice| {
+                let invoice_id: InvoiceId = match parse_id("invoice", &invoice.id) {
+                    Ok(invoice_id) => invoice_id,
+                    Err(error) => {
+                        invalid_data.push(error);
+                        return None;
+                    }
+                };
+                store.invoices.insert(
+                    invoice_id,
+                    Invoice {
+                        time_block_id,
+                        custom_id: invoice.custom_id,
+                        url: invoice.url,
+                    },
+                );
+                Some(invoice_id)
+            });
+            store.time_blocks.insert(
+                time_block_id,
+                TimeBlock {
+                    client_id,
+                    name: time_block.name,
+                    status: time_block.status.into(),
+                    duration: Duration::seconds(i64::from(time_block.duration)),
+                    invoice_id,
+                },
+            );
+        }
+    }
+    Ok((store, invalid_data))
+}
+
+fn time_entry_from_response(
+    project_id: ProjectId,
+    time_entry: graphql::queries::clients_with_all_descendants::TimeEntry,
+) -> graphql::Result<(TimeEntryId, TimeEntry)> {
+    let time_entry_id = parse_id("time_entry", &time_entry.id)?;
+    let started = parse_time("time_entry", &time_entry.id, "started", &time_entry.started.0)?;
+    let stopped = time_entry
+        .stopped
+        .map(|stopped| parse_time("time_entry", &time_entry.id, "stopped", &stopped.0))
+        .transpose()?;
+    Ok((
+        time_entry_id,
+        TimeEntry {
+            project_id,
+            name: time_entry.name,
+            started,
+            stopped,
+        },
+    ))
+}
+
+fn parse_id(entity: &'static str, id: &str) -> graphql::Result<Ulid> {
+    id.parse().map_err(|error: ulid::DecodeError| graphql::GraphQLError::InvalidData {
+        entity,
+        id: id.to_owned(),
+        field: "id",
+        reason: error.to_string(),
+    })
+}
+
+fn parse_time(
+    entity: &'static str,
+    id: &str,
+    field: &'static str,
+    time: &str,
+) -> graphql::Result<DateTime<Local>> {
+    time.parse().map_err(|error: chrono::ParseError| graphql::GraphQLError::InvalidData {
+        entity,
+        id: id.to_owned(),
+        field,
+        reason: error.to_string(),
+    })
+}
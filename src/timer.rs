@@ -0,0 +1,138 @@
+use chrono::prelude::*;
+use cynic::MutationBuilder;
+use ulid::Ulid;
+
+use crate::graphql;
+use crate::store::{self, Snapshot, Store};
+
+// ------ Save ------
+
+/// Optimistic change of the store and the request that saves it.
+///
+/// Both the time tracker page and the app, when the page isn't open, start and stop timers here.
+pub struct Save {
+    pub change: store::Change,
+    /// The request can be sent again when it has failed.
+    pub request: graphql::Result<graphql::Replay>,
+}
+
+/// Starts a time entry in the project, the running ones are stopped if there can be only one.
+pub fn start(
+    store: &mut Store,
+    graphql_config: &graphql::Config,
+    project_id: Ulid,
+    name: String,
+    single_active_timer: bool,
+) -> Save {
+    let started = Local::now();
+    let started_date_time = graphql::types::DateTime(started.to_rfc3339());
+
+    let time_entry_id = Ulid::new();
+    let stopped_time_entry_ids = if single_active_timer {
+        store
+            .time_entries
+            .iter()
+            .filter(|(_, time_entry)| time_entry.stopped.is_none())
+            .map(|(time_entry_id, _)| *time_entry_id)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let before = Snapshot::default().time_entries(
+        store,
+        stopped_time_entry_ids.iter().copied().chain(Some(time_entry_id)),
+    );
+
+    for stopped_time_entry_id in &stopped_time_entry_ids {
+        if let Some(time_entry) = store.time_entries.get_mut(stopped_time_entry_id) {
+            time_entry.stopped = Some(started);
+        }
+    }
+    store.time_entries.insert(
+        time_entry_id,
+        store::TimeEntry {
+            project_id,
+            name: name.clone(),
+            started,
+            stopped: None,
+        },
+    );
+    let change = store.change_since(before);
+
+    let input = graphql::types::AddTimeEntryInput {
+        id: time_entry_id.to_string(),
+        name,
+        started: started_date_time.clone(),
+        stopped: None,
+        project: graphql::types::ProjectRef {
+            id: Some(project_id.to_string()),
+        },
+    };
+    let running_time_entries = graphql::types::TimeEntryFilter::any_of(
+        stopped_time_entry_ids.iter().map(ToString::to_string),
+    );
+    let request = match running_time_entries {
+        None => {
+            use graphql::mutations::add_time_entry as mutation_mod;
+
+            let operation =
+                mutation_mod::Mutation::build(mutation_mod::Arguments { input: vec![input] });
+            graphql::Replay::new(graphql_config, &operation)
+        }
+        Some(running_time_entries) => {
+            // Stop the running time entries and start the new one in one request.
+            use graphql::mutations::stop_and_add_time_entry as mutation_mod;
+
+            let stop = graphql::types::UpdateTimeEntryInput::new(
+                running_time_entries,
+                graphql::types::TimeEntryPatch {
+                    stopped: Some(started_date_time),
+                    ..Default::default()
+                },
+            );
+            let operation = mutation_mod::Mutation::build(mutation_mod::Arguments {
+                stop,
+                input: vec![input],
+            });
+            graphql::Replay::new(graphql_config, &operation)
+        }
+    };
+    Save { change, request }
+}
+
+/// Stops the running time entries of the project.
+pub fn stop(store: &mut Store, graphql_config: &graphql::Config, project_id: Ulid) -> Vec<Save> {
+    use graphql::mutations::update_time_entry as mutation_mod;
+
+    let stopped = Local::now();
+    let running_time_entry_ids = store
+        .time_entries
+        .iter()
+        .filter(|(_, time_entry)| {
+            time_entry.project_id == project_id && time_entry.stopped.is_none()
+        })
+        .map(|(time_entry_id, _)| *time_entry_id)
+        .collect::<Vec<_>>();
+
+    running_time_entry_ids
+        .into_iter()
+        .map(|time_entry_id| {
+            let before = Snapshot::default().time_entries(store, Some(time_entry_id));
+            if let Some(time_entry) = store.time_entries.get_mut(&time_entry_id) {
+                time_entry.stopped = Some(stopped);
+            }
+            let input = graphql::types::UpdateTimeEntryInput::new(
+                graphql::types::TimeEntryFilter::id(time_entry_id),
+                graphql::types::TimeEntryPatch {
+                    stopped: Some(graphql::types::DateTime(stopped.to_rfc3339())),
+                    ..Default::default()
+                },
+            );
+            let operation = mutation_mod::Mutation::build(mutation_mod::Arguments { input });
+            Save {
+                change: store.change_since(before),
+                request: graphql::Replay::new(graphql_config, &operation),
+            }
+        })
+        .collect()
+}